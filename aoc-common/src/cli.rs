@@ -0,0 +1,179 @@
+use crate::{read_input_arg, Part, Solution, INPUT_DIR_VAR};
+use anyhow::{anyhow, Context, Result};
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    input: Option<String>,
+    parts: Vec<Part>,
+    help: bool,
+}
+
+// "day07" -> "day 07", as used in benchmark names
+pub fn display_name(name: &str) -> String {
+    match name.strip_prefix("day") {
+        Some(number) => format!("day {}", number),
+        None => name.to_string(),
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options> {
+    let mut options = Options {
+        input: None,
+        parts: vec![Part::One, Part::Two],
+        help: false,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-i" | "--input" => {
+                options.input = Some(args.next().ok_or_else(|| anyhow!("{} needs a path", arg))?);
+            }
+            "-p" | "--part" => {
+                let part = args.next().ok_or_else(|| anyhow!("{} needs 1 or 2", arg))?;
+                let part = part
+                    .parse::<u8>()
+                    .with_context(|| format!("Invalid part: {}", part))?;
+                options.parts = vec![Part::try_from(part)?];
+            }
+            "-h" | "--help" => options.help = true,
+            _ => return Err(anyhow!("Unexpected argument: {}", arg)),
+        }
+    }
+    Ok(options)
+}
+
+/// Entry point generated by [`aoc_main!`](crate::aoc_main) for each day's binary.
+pub fn run_main<S: Solution>(name: &str) -> Result<()> {
+    let options = parse_args(std::env::args().skip(1))?;
+    if options.help {
+        println!("Usage: {} [--input <path>] [--part <1|2>]", name);
+        println!(
+            "  -i, --input <path>  puzzle input (default: embedded, or {}.txt in {} or inputs/)",
+            name, INPUT_DIR_VAR
+        );
+        println!("  -p, --part <1|2>    solve a single part (default: both)");
+        return Ok(());
+    }
+
+    let input = read_input_arg::<S>(options.input.as_deref(), name)?;
+    let parsed = S::parse(&input)?;
+
+    for part in options.parts {
+        match part {
+            Part::One => println!("Part 1: {}", S::part1(&parsed)),
+            Part::Two => println!("Part 2: {}", S::part2(&parsed)),
+        }
+    }
+    Ok(())
+}
+
+/// Generate a day's `main` from its [`Solution`] type.
+///
+/// The binary accepts `--input <path>` and `--part <1|2>`; without `--input` it
+/// uses the embedded input or resolves `inputs/dayNN.txt`.
+#[macro_export]
+macro_rules! aoc_main {
+    ($solution:ty) => {
+        fn main() -> ::anyhow::Result<()> {
+            $crate::run_main::<$solution>(env!("CARGO_PKG_NAME"))
+        }
+    };
+}
+
+/// Generate a day's criterion benchmarks from its [`Solution`] type:
+/// part 1, part 2, and parse + part 1/2 from the raw input. Extra benchmark
+/// functions of the day's own can follow the type.
+///
+/// The bench target needs `criterion` as a dev-dependency.
+#[macro_export]
+macro_rules! aoc_bench {
+    ($solution:ty $(, $extra:path)* $(,)?) => {
+        fn read_bench_input() -> String {
+            $crate::read_input::<$solution>(env!("CARGO_PKG_NAME")).unwrap()
+        }
+
+        fn benchmark_part1(c: &mut ::criterion::Criterion) {
+            let name = $crate::display_name(env!("CARGO_PKG_NAME"));
+            let parsed = <$solution as $crate::Solution>::parse(&read_bench_input()).unwrap();
+            c.bench_function(&format!("{} - part 1", name), |b| {
+                b.iter(|| <$solution as $crate::Solution>::part1(::criterion::black_box(&parsed)))
+            });
+        }
+
+        fn benchmark_part2(c: &mut ::criterion::Criterion) {
+            let name = $crate::display_name(env!("CARGO_PKG_NAME"));
+            let parsed = <$solution as $crate::Solution>::parse(&read_bench_input()).unwrap();
+            c.bench_function(&format!("{} - part 2", name), |b| {
+                b.iter(|| <$solution as $crate::Solution>::part2(::criterion::black_box(&parsed)))
+            });
+        }
+
+        fn benchmark_full_solution(c: &mut ::criterion::Criterion) {
+            let name = $crate::display_name(env!("CARGO_PKG_NAME"));
+            let mut group = c.benchmark_group(format!("{} - full solution", name));
+            let input = read_bench_input();
+
+            group.bench_function("parse + part1", |b| {
+                b.iter(|| {
+                    let parsed =
+                        <$solution as $crate::Solution>::parse(::criterion::black_box(&input))
+                            .unwrap();
+                    <$solution as $crate::Solution>::part1(&parsed)
+                })
+            });
+
+            group.bench_function("parse + part2", |b| {
+                b.iter(|| {
+                    let parsed =
+                        <$solution as $crate::Solution>::parse(::criterion::black_box(&input))
+                            .unwrap();
+                    <$solution as $crate::Solution>::part2(&parsed)
+                })
+            });
+
+            group.finish();
+        }
+
+        ::criterion::criterion_group!(
+            benches,
+            benchmark_part1,
+            benchmark_part2,
+            benchmark_full_solution
+            $(, $extra)*
+        );
+        ::criterion::criterion_main!(benches);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() -> Result<()> {
+        let options = parse_args(args(&[]))?;
+        assert_eq!(options.input, None);
+        assert_eq!(options.parts, vec![Part::One, Part::Two]);
+
+        let options = parse_args(args(&["--input", "other.txt", "-p", "2"]))?;
+        assert_eq!(options.input, Some("other.txt".to_string()));
+        assert_eq!(options.parts, vec![Part::Two]);
+
+        assert!(parse_args(args(&["--help"]))?.help);
+        assert!(parse_args(args(&["--part", "3"])).is_err());
+        assert!(parse_args(args(&["--input"])).is_err());
+        assert!(parse_args(args(&["extra"])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_name() {
+        assert_eq!(display_name("day07"), "day 07");
+        assert_eq!(display_name("other"), "other");
+    }
+}
@@ -0,0 +1,106 @@
+use crate::Solution;
+use anyhow::{anyhow, Context, Result};
+use std::env;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing at the directory holding the `dayNN.txt` inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Locate `<name>.txt`: in `$AOC_INPUT_DIR` when set, otherwise in an `inputs`
+/// directory of the current directory or one of its parents, falling back to
+/// the workspace root this crate was built from.
+pub fn resolve_input_path(name: &str) -> Result<PathBuf> {
+    let env_dir = env::var_os(INPUT_DIR_VAR).map(PathBuf::from);
+    let cwd = env::current_dir().ok();
+    resolve_in(name, env_dir, cwd.as_deref())
+}
+
+fn resolve_in(name: &str, env_dir: Option<PathBuf>, cwd: Option<&Path>) -> Result<PathBuf> {
+    let file_name = format!("{}.txt", name);
+
+    // An explicit directory wins, even if the file is missing there
+    if let Some(dir) = env_dir {
+        return Ok(dir.join(file_name));
+    }
+
+    let mut dirs: Vec<PathBuf> = cwd
+        .into_iter()
+        .flat_map(Path::ancestors)
+        .map(|dir| dir.join("inputs"))
+        .collect();
+    dirs.push(Path::new(env!("CARGO_MANIFEST_DIR")).join("../inputs"));
+
+    dirs.iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            anyhow!(
+                "Could not find {} in an inputs directory, set {} to point at one",
+                file_name,
+                INPUT_DIR_VAR
+            )
+        })
+}
+
+/// A day's puzzle input: the embedded copy when available, otherwise the resolved file.
+pub fn read_input<S: Solution>(name: &str) -> Result<String> {
+    if let Some(input) = S::INPUT {
+        return Ok(input.to_string());
+    }
+    let path = resolve_input_path(name)?;
+    read_to_string(&path).with_context(|| format!("Could not read input file {}", path.display()))
+}
+
+/// The input file given on the command line, or [`read_input`] without one.
+pub fn read_input_arg<S: Solution>(path: Option<&str>, name: &str) -> Result<String> {
+    match path {
+        Some(path) => {
+            read_to_string(path).with_context(|| format!("Could not read input file {}", path))
+        }
+        None => read_input::<S>(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::Echo;
+    use std::fs;
+
+    #[test]
+    fn test_resolve_in() -> Result<()> {
+        let workspace = tempfile::tempdir()?;
+        let day_dir = workspace.path().join("day07");
+        fs::create_dir_all(workspace.path().join("inputs"))?;
+        fs::create_dir_all(&day_dir)?;
+        fs::write(workspace.path().join("inputs/day07.txt"), "123 -> a")?;
+
+        // From the workspace root and from a day's directory
+        let expected = workspace.path().join("inputs/day07.txt");
+        assert_eq!(resolve_in("day07", None, Some(workspace.path()))?, expected);
+        assert_eq!(resolve_in("day07", None, Some(&day_dir))?, expected);
+
+        let other = PathBuf::from("/somewhere/else");
+        assert_eq!(
+            resolve_in("day07", Some(other.clone()), Some(&day_dir))?,
+            other.join("day07.txt")
+        );
+
+        assert!(resolve_in("day42", None, Some(&day_dir)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_input_arg() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("mine.txt");
+        fs::write(&path, "abc")?;
+        assert_eq!(read_input_arg::<Echo>(path.to_str(), "day25")?, "abc");
+
+        let missing = dir.path().join("missing.txt");
+        let error = read_input_arg::<Echo>(missing.to_str(), "day25").unwrap_err();
+        assert!(error.to_string().starts_with("Could not read input file"));
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+//! The interface shared by every day. The traits only need `core` + `alloc`;
+//! reading inputs and the command line driver need the default `std` feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use anyhow::{anyhow, Result};
+use core::fmt::{self, Display};
+
+#[cfg(feature = "std")]
+mod cli;
+#[cfg(feature = "std")]
+mod input;
+mod metadata;
+
+#[cfg(feature = "std")]
+pub use cli::{display_name, run_main};
+#[cfg(feature = "std")]
+pub use input::{read_input, read_input_arg, resolve_input_path, INPUT_DIR_VAR};
+pub use metadata::{Answer, AnswerKind, Metadata, Tag, YEAR};
+
+/// Shared interface implemented by every day's solver so it can be driven
+/// generically (runner, HTTP server, ...) from the raw puzzle input.
+pub trait Solution {
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    const METADATA: Metadata;
+
+    /// Puzzle input baked into the binary, see [`embedded_input!`].
+    const INPUT: Option<&'static str> = None;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+/// Define a day's `INPUT`: its puzzle input baked in at build time when the
+/// crate's `embed-inputs` feature is enabled, `None` otherwise.
+#[macro_export]
+macro_rules! embedded_input {
+    () => {
+        /// Puzzle input embedded at build time (`embed-inputs` feature).
+        #[cfg(feature = "embed-inputs")]
+        pub const INPUT: Option<&str> = Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../inputs/",
+            env!("CARGO_PKG_NAME"),
+            ".txt"
+        )));
+        /// Puzzle input embedded at build time (`embed-inputs` feature).
+        #[cfg(not(feature = "embed-inputs"))]
+        pub const INPUT: Option<&str> = None;
+    };
+}
+
+/// Optional capability for days that can walk through how an answer was reached.
+pub trait Explain: Solution {
+    fn explain(input: &Self::Input, part: Part) -> String;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(anyhow!("Unknown part: {}", value)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// Parse the raw input and solve a single part, rendering the answer as text
+pub fn solve<S: Solution>(part: Part, input: &str) -> Result<String> {
+    let parsed = S::parse(input)?;
+    Ok(match part {
+        Part::One => S::part1(&parsed).to_string(),
+        Part::Two => S::part2(&parsed).to_string(),
+    })
+}
+
+// Parse the raw input and describe how a single part is solved
+pub fn explain<S: Explain>(part: Part, input: &str) -> Result<String> {
+    let parsed = S::parse(input)?;
+    Ok(S::explain(&parsed, part))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) struct Echo;
+
+    impl Solution for Echo {
+        type Input = String;
+        type Part1 = usize;
+        type Part2 = String;
+
+        const METADATA: Metadata = Metadata {
+            day: 25,
+            title: "Echo",
+            input: "Any text",
+            part1: Answer {
+                label: "length",
+                kind: AnswerKind::Integer,
+            },
+            part2: Answer {
+                label: "shouted",
+                kind: AnswerKind::Text,
+            },
+            tags: &[Tag::Strings],
+        };
+
+        fn parse(input: &str) -> Result<String> {
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &String) -> usize {
+            input.len()
+        }
+
+        fn part2(input: &String) -> String {
+            input.to_uppercase()
+        }
+    }
+
+    #[test]
+    fn test_part_from_number() {
+        assert_eq!(Part::try_from(1).unwrap(), Part::One);
+        assert_eq!(Part::try_from(2).unwrap(), Part::Two);
+        assert!(Part::try_from(3).is_err());
+    }
+
+    #[test]
+    fn test_metadata() {
+        let metadata = Echo::METADATA;
+        assert_eq!(metadata.url(), "https://adventofcode.com/2015/day/25");
+        assert_eq!(metadata.answer(Part::Two).kind, AnswerKind::Text);
+        assert_eq!(Tag::BruteForce.to_string(), "brute-force");
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve::<Echo>(Part::One, "abc").unwrap(), "3");
+        assert_eq!(solve::<Echo>(Part::Two, "abc").unwrap(), "ABC");
+    }
+}
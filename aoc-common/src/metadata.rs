@@ -0,0 +1,80 @@
+use crate::Part;
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{self, Display};
+
+pub const YEAR: u16 = 2015;
+
+/// What a day's puzzle is about, exported by each day crate as `METADATA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    pub day: u8,
+    pub title: &'static str,
+    /// Short description of the puzzle input's format
+    pub input: &'static str,
+    pub part1: Answer,
+    pub part2: Answer,
+    pub tags: &'static [Tag],
+}
+
+impl Metadata {
+    pub fn url(&self) -> String {
+        format!("https://adventofcode.com/{}/day/{}", YEAR, self.day)
+    }
+
+    pub fn answer(&self, part: Part) -> Answer {
+        match part {
+            Part::One => self.part1,
+            Part::Two => self.part2,
+        }
+    }
+}
+
+/// What a part's answer is, e.g. the "final floor" as an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Answer {
+    pub label: &'static str,
+    pub kind: AnswerKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerKind {
+    Integer,
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tag {
+    BruteForce,
+    Graph,
+    Grid,
+    Hashing,
+    Math,
+    Parsing,
+    Simulation,
+    Strings,
+}
+
+impl Display for AnswerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AnswerKind::Integer => "integer",
+            AnswerKind::Text => "text",
+        })
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Tag::BruteForce => "brute-force",
+            Tag::Graph => "graph",
+            Tag::Grid => "grid",
+            Tag::Hashing => "hashing",
+            Tag::Math => "math",
+            Tag::Parsing => "parsing",
+            Tag::Simulation => "simulation",
+            Tag::Strings => "strings",
+        })
+    }
+}
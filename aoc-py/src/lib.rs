@@ -0,0 +1,334 @@
+use aoc2015::day07::Circuit;
+use aoc2015::day09;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+// pyo3 turns a Rust panic into a Python exception by unwinding, aborting would kill the interpreter
+#[cfg(panic = "abort")]
+compile_error!("aoc-py must be built with panic = \"unwind\"");
+
+create_exception!(
+    aoc2015,
+    AocError,
+    PyException,
+    "Base class for solver errors."
+);
+create_exception!(
+    aoc2015,
+    ParseError,
+    AocError,
+    "The puzzle input is invalid."
+);
+create_exception!(
+    aoc2015,
+    CircuitError,
+    AocError,
+    "A wire can't be evaluated."
+);
+
+fn parse_error(e: anyhow::Error) -> PyErr {
+    ParseError::new_err(format!("{:#}", e))
+}
+
+fn solve_error(e: anyhow::Error) -> PyErr {
+    AocError::new_err(format!("{:#}", e))
+}
+
+fn circuit_error(e: anyhow::Error) -> PyErr {
+    CircuitError::new_err(format!("{:#}", e))
+}
+
+// Answer of a day that solves straight from unchecked lines
+fn checked<T>(answer: anyhow::Result<T>) -> PyResult<T> {
+    answer.map_err(parse_error)
+}
+
+// Attach `aoc2015.<name>` and register it in `sys.modules` so it can be imported directly
+fn add_submodule<'py>(parent: &Bound<'py, PyModule>, name: &str) -> PyResult<Bound<'py, PyModule>> {
+    let py = parent.py();
+    let module = PyModule::new(py, name)?;
+    parent.add_submodule(&module)?;
+    py.import("sys")?
+        .getattr("modules")?
+        .set_item(format!("aoc2015.{}", name), &module)?;
+    Ok(module)
+}
+
+// Wrap a day's `parse`, `solve_part1` and `solve_part2` as a Python submodule,
+// passing answers through `$answer` when the solvers return a `Result`
+macro_rules! day_module {
+    ($module:ident, $day:ident, $input:ty, $part1:ty, $part2:ty) => {
+        day_module!($module, $day, $input, $part1, $part2, Ok);
+    };
+    ($module:ident, $day:ident, $input:ty, $part1:ty, $part2:ty, $answer:path) => {
+        mod $module {
+            use aoc2015::$day;
+            use pyo3::prelude::*;
+
+            #[pyfunction]
+            fn parse(input: &str) -> PyResult<$input> {
+                $day::parse(input).map_err(crate::parse_error)
+            }
+
+            #[pyfunction]
+            fn solve_part1(input: $input) -> PyResult<$part1> {
+                $answer($day::solve_part1(&input))
+            }
+
+            #[pyfunction]
+            fn solve_part2(input: $input) -> PyResult<$part2> {
+                $answer($day::solve_part2(&input))
+            }
+
+            pub fn register<'py>(parent: &Bound<'py, PyModule>) -> PyResult<Bound<'py, PyModule>> {
+                let module = crate::add_submodule(parent, stringify!($day))?;
+                module.add_function(wrap_pyfunction!(parse, &module)?)?;
+                module.add_function(wrap_pyfunction!(solve_part1, &module)?)?;
+                module.add_function(wrap_pyfunction!(solve_part2, &module)?)?;
+                Ok(module)
+            }
+        }
+    };
+}
+
+day_module!(py_day01, day01, Vec<i32>, i32, Option<usize>);
+day_module!(py_day03, day03, Vec<char>, i32, i32);
+day_module!(py_day04, day04, String, i32, i32);
+day_module!(py_day05, day05, Vec<String>, usize, usize);
+day_module!(py_day06, day06, Vec<String>, u32, u32);
+day_module!(py_day07, day07, Vec<String>, u16, u16, crate::checked);
+day_module!(py_day08, day08, Vec<String>, usize, usize);
+day_module!(py_day09, day09, Vec<String>, usize, usize, crate::checked);
+day_module!(py_day10, day10, String, usize, usize);
+day_module!(py_day11, day11, String, String, String);
+
+// Day 2 presents cross over as `(l, w, h)` tuples
+mod py_day02 {
+    use anyhow::Context;
+    use aoc2015::day02::{self, Present};
+    use pyo3::prelude::*;
+
+    // Tuples are checked like parsed lines, numbered from 1
+    fn presents(dimensions: Vec<(u32, u32, u32)>) -> PyResult<Vec<Present>> {
+        dimensions
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (l, w, h))| {
+                Present::new(l, w, h).with_context(|| format!("Present {}", idx + 1))
+            })
+            .collect::<anyhow::Result<Vec<Present>>>()
+            .map_err(crate::parse_error)
+    }
+
+    #[pyfunction]
+    fn parse(input: &str) -> PyResult<Vec<(u32, u32, u32)>> {
+        let presents = day02::parse(input).map_err(crate::parse_error)?;
+        Ok(presents.iter().map(|p| (p.l, p.w, p.h)).collect())
+    }
+
+    #[pyfunction]
+    fn solve_part1(input: Vec<(u32, u32, u32)>) -> PyResult<u128> {
+        day02::solve_part1(&presents(input)?).map_err(crate::solve_error)
+    }
+
+    #[pyfunction]
+    fn solve_part2(input: Vec<(u32, u32, u32)>) -> PyResult<u128> {
+        day02::solve_part2(&presents(input)?).map_err(crate::solve_error)
+    }
+
+    pub fn register<'py>(parent: &Bound<'py, PyModule>) -> PyResult<Bound<'py, PyModule>> {
+        let module = crate::add_submodule(parent, "day02")?;
+        module.add_function(wrap_pyfunction!(parse, &module)?)?;
+        module.add_function(wrap_pyfunction!(solve_part1, &module)?)?;
+        module.add_function(wrap_pyfunction!(solve_part2, &module)?)?;
+        Ok(module)
+    }
+}
+
+/// A day 7 circuit of wires and logic gates.
+#[pyclass(name = "Circuit", module = "aoc2015.day07")]
+struct PyCircuit(Circuit);
+
+#[pymethods]
+impl PyCircuit {
+    #[new]
+    fn new(lines: Vec<String>) -> PyResult<PyCircuit> {
+        Circuit::from_lines(&lines)
+            .map(PyCircuit)
+            .map_err(parse_error)
+    }
+
+    fn evaluate(&self, wire: &str) -> PyResult<u16> {
+        self.0.evaluate(wire).map_err(circuit_error)
+    }
+
+    fn set_signal(&mut self, wire: &str, value: u16) {
+        self.0.set_signal(wire, value)
+    }
+
+    fn wires(&self) -> Vec<String> {
+        let mut wires: Vec<String> = self.0.wires().map(String::from).collect();
+        wires.sort();
+        wires
+    }
+}
+
+/// Shortest route visiting every city once, as `(distance, cities)`.
+#[pyfunction]
+fn shortest_route(lines: Vec<String>) -> PyResult<(usize, Vec<String>)> {
+    day09::shortest_route(&lines).map_err(parse_error)
+}
+
+/// Longest route visiting every city once, as `(distance, cities)`.
+#[pyfunction]
+fn longest_route(lines: Vec<String>) -> PyResult<(usize, Vec<String>)> {
+    day09::longest_route(&lines).map_err(parse_error)
+}
+
+#[pymodule]
+#[pyo3(name = "aoc2015")]
+fn aoc2015_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = module.py();
+    module.add("AocError", py.get_type::<AocError>())?;
+    module.add("ParseError", py.get_type::<ParseError>())?;
+    module.add("CircuitError", py.get_type::<CircuitError>())?;
+
+    py_day01::register(module)?;
+    py_day02::register(module)?;
+    py_day03::register(module)?;
+    py_day04::register(module)?;
+    py_day05::register(module)?;
+    py_day06::register(module)?;
+    let day07 = py_day07::register(module)?;
+    day07.add_class::<PyCircuit>()?;
+    py_day08::register(module)?;
+    let day09 = py_day09::register(module)?;
+    day09.add_function(wrap_pyfunction!(shortest_route, &day09)?)?;
+    day09.add_function(wrap_pyfunction!(longest_route, &day09)?)?;
+    py_day10::register(module)?;
+    py_day11::register(module)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+    use std::ffi::CString;
+
+    fn run(code: &str) -> PyResult<()> {
+        Python::with_gil(|py| {
+            let module = pyo3::wrap_pymodule!(aoc2015_py)(py);
+            let locals = PyDict::new(py);
+            locals.set_item("aoc2015", module)?;
+            py.run(&CString::new(code).unwrap(), None, Some(&locals))
+        })
+    }
+
+    #[pyfunction]
+    fn buggy_solver() -> usize {
+        panic!("solver bug")
+    }
+
+    // Python sees the panic as an exception it can catch
+    #[test]
+    fn test_panic_raises() {
+        Python::with_gil(|py| {
+            let locals = PyDict::new(py);
+            locals.set_item("solve", wrap_pyfunction!(buggy_solver, py)?)?;
+            let code = c"
+try:
+    solve()
+    raise AssertionError('expected PanicException')
+except BaseException as e:
+    assert type(e).__name__ == 'PanicException' and str(e) == 'solver bug'
+";
+            py.run(code, None, Some(&locals))
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_day_functions() {
+        run(r#"
+directions = aoc2015.day01.parse("(()(()(")
+assert directions == [1, 1, -1, 1, 1, -1, 1]
+assert aoc2015.day01.solve_part1(directions) == 3
+assert aoc2015.day01.solve_part2(directions) is None
+assert aoc2015.day01.solve_part2(aoc2015.day01.parse("()())")) == 5
+assert aoc2015.day02.parse("2x3x4\n1x1x10") == [(2, 3, 4), (1, 1, 10)]
+assert aoc2015.day02.solve_part1([(2, 3, 4)]) == 58
+assert aoc2015.day03.solve_part2(aoc2015.day03.parse("^v")) == 3
+assert aoc2015.day11.solve_part1("abcdefgh") == "abcdffaa"
+"#)
+        .unwrap();
+    }
+
+    #[test]
+    fn test_circuit_and_routes() {
+        run(r#"
+circuit = aoc2015.day07.Circuit(["123 -> x", "NOT x -> h"])
+assert circuit.evaluate("h") == 65412
+assert circuit.wires() == ["h", "x"]
+circuit.set_signal("x", 1)
+assert circuit.evaluate("h") == 65534
+
+lines = ["London to Dublin = 464", "London to Belfast = 518", "Dublin to Belfast = 141"]
+distance, route = aoc2015.day09.shortest_route(lines)
+assert distance == 605 and len(route) == 3
+assert aoc2015.day09.longest_route(lines)[0] == 982
+assert aoc2015.day09.solve_part1(lines) == 605
+assert aoc2015.day07.solve_part1(["123 -> a"]) == 123
+"#)
+        .unwrap();
+    }
+
+    #[test]
+    fn test_errors_map_to_exceptions() {
+        run(r#"
+try:
+    aoc2015.day07.Circuit(["x INVALID y -> z"])
+    raise AssertionError("expected ParseError")
+except aoc2015.ParseError as e:
+    assert isinstance(e, aoc2015.AocError)
+
+try:
+    aoc2015.day07.Circuit(["a -> b"]).evaluate("b")
+    raise AssertionError("expected CircuitError")
+except aoc2015.CircuitError:
+    pass
+
+try:
+    aoc2015.day09.shortest_route(["Invalid line"])
+    raise AssertionError("expected ParseError")
+except aoc2015.ParseError:
+    pass
+
+try:
+    aoc2015.day07.solve_part1(["x INVALID y -> a"])
+    raise AssertionError("expected ParseError")
+except aoc2015.ParseError:
+    pass
+
+try:
+    aoc2015.day09.solve_part1(["bad"])
+    raise AssertionError("expected ParseError")
+except aoc2015.ParseError:
+    pass
+
+try:
+    aoc2015.day02.parse("2x3x4\n2x0x4")
+    raise AssertionError("expected ParseError")
+except aoc2015.ParseError as e:
+    assert str(e).startswith("Line 2: ")
+
+try:
+    aoc2015.day02.solve_part1([(2, 3, 4), (0, 0, 0)])
+    raise AssertionError("expected ParseError")
+except aoc2015.ParseError as e:
+    assert str(e) == "Present 2: Dimensions must be positive, found 0"
+"#)
+        .unwrap();
+    }
+}
@@ -0,0 +1,29 @@
+use anyhow::{anyhow, Result};
+use aoc2015::Part;
+use wasm_bindgen::prelude::*;
+
+/// Solve one part of a day from its puzzle input, throwing on invalid arguments or input.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    solve_input(day, part, input).map_err(|e| JsError::new(&format!("{:#}", e)))
+}
+
+fn solve_input(day: u8, part: u8, input: &str) -> Result<String> {
+    let part = Part::try_from(part)?;
+    let day = aoc2015::find(day).ok_or_else(|| anyhow!("Unknown day: {}", day))?;
+    (day.solve)(part, input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_input() {
+        assert_eq!(solve_input(1, 1, "(()(()(").unwrap(), "3");
+        assert_eq!(solve_input(1, 2, "()())").unwrap(), "5");
+        assert_eq!(solve_input(3, 2, "^v^v^v^v^v").unwrap(), "11");
+        assert!(solve_input(12, 1, "").is_err());
+        assert!(solve_input(1, 3, "").is_err());
+    }
+}
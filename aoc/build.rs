@@ -0,0 +1,73 @@
+// Fingerprint every day's sources so cached answers are dropped when a solver changes
+use md5::{Digest, Md5};
+use std::env;
+use std::fs::{self, read_dir};
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let workspace = manifest_dir.parent().unwrap();
+
+    // Answers also depend on the shared interface the days are driven through
+    let mut common = Md5::new();
+    hash_crate(&workspace.join("aoc-common"), &mut common);
+    let common = common.finalize();
+
+    let mut days: Vec<PathBuf> = read_dir(workspace)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.starts_with("day") && path.join("Cargo.toml").exists()
+        })
+        .collect();
+    days.sort();
+
+    let mut arms = String::new();
+    for day in days {
+        let mut hasher = Md5::new();
+        hasher.update(common);
+        hash_crate(&day, &mut hasher);
+        let name = day.file_name().unwrap().to_string_lossy();
+        let version = format!("{:x}", hasher.finalize());
+        arms.push_str(&format!(
+            "        {:?} => Some({:?}),\n",
+            name,
+            &version[..16]
+        ));
+    }
+
+    let code = format!(
+        "pub fn solver_version(day: &str) -> Option<&'static str> {{\n    match day {{\n{}        _ => None,\n    }}\n}}\n",
+        arms
+    );
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("solver_versions.rs"), code).unwrap();
+}
+
+fn hash_crate(dir: &Path, hasher: &mut Md5) {
+    let manifest = dir.join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", manifest.display());
+    hasher.update(fs::read(&manifest).unwrap());
+
+    let src = dir.join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+    hash_dir(&src, hasher);
+}
+
+fn hash_dir(dir: &Path, hasher: &mut Md5) {
+    let mut entries: Vec<PathBuf> = read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            hash_dir(&path, hasher);
+        } else {
+            hasher.update(path.file_name().unwrap().to_string_lossy().as_bytes());
+            hasher.update(fs::read(&path).unwrap());
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use crate::table;
+use crate::worker::{self, Job, Outcome, Pool};
+use anyhow::{anyhow, Context, Result};
+use aoc2015::{Day, Part};
+use clap::Args;
+use std::collections::VecDeque;
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Args)]
+pub struct Config {
+    /// Day whose solver is run on every input
+    #[arg(short, long)]
+    pub day: u8,
+
+    /// Part to solve, both parts when omitted
+    #[arg(short, long)]
+    pub part: Option<u8>,
+
+    /// Time allowed for each part before the input is flagged
+    #[arg(long, default_value_t = 10)]
+    pub timeout_secs: u64,
+
+    /// Directory of puzzle inputs, one per file
+    pub dir: PathBuf,
+}
+
+// One input file and how each selected part went on it
+struct Row {
+    file: String,
+    outcomes: Vec<Outcome>,
+}
+
+impl Row {
+    fn flagged(&self) -> bool {
+        !self
+            .outcomes
+            .iter()
+            .all(|outcome| matches!(outcome, Outcome::Solved(..)))
+    }
+}
+
+pub fn batch(config: Config) -> Result<()> {
+    let day = aoc2015::find(config.day).ok_or_else(|| anyhow!("Unknown day: {}", config.day))?;
+    let parts = crate::select_parts(config.part)?;
+    let files = input_files(&config.dir)?;
+    if files.is_empty() {
+        return Err(anyhow!("No input files in {}", config.dir.display()));
+    }
+
+    let rows = solve_files(
+        day,
+        &parts,
+        &files,
+        Duration::from_secs(config.timeout_secs),
+    );
+    print!("{}", render(&parts, &rows));
+
+    let flagged = rows.iter().filter(|row| row.flagged()).count();
+    if flagged > 0 {
+        return Err(anyhow!("{} of {} inputs flagged", flagged, rows.len()));
+    }
+    Ok(())
+}
+
+fn input_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in read_dir(dir).with_context(|| format!("Could not read {}", dir.display()))? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// Files and parts are solved on a worker per core, the oldest job waited on
+// before the next is queued so timings don't depend on how many files there are
+fn solve_files(day: Day, parts: &[Part], files: &[PathBuf], timeout: Duration) -> Vec<Row> {
+    let workers = worker::parallelism();
+    let pool = Pool::new(workers, workers);
+    let mut rows: Vec<Row> = Vec::with_capacity(files.len());
+    let mut running: VecDeque<(usize, Option<Job>)> = VecDeque::with_capacity(workers);
+
+    // Jobs finish in the order they were queued, so each row gets its parts in order.
+    // The queue only fills up behind stuck workers, so a job turned away is not run
+    let finish = |rows: &mut Vec<Row>, (row, job): (usize, Option<Job>)| {
+        rows[row]
+            .outcomes
+            .push(job.map_or(Outcome::NotRun, Job::wait));
+    };
+
+    for path in files {
+        let file = path.file_name().unwrap().to_string_lossy().into_owned();
+        let input =
+            read_to_string(path).with_context(|| format!("Could not read {}", path.display()));
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                let outcomes = parts
+                    .iter()
+                    .map(|_| Outcome::Failed(anyhow!("{:#}", e)))
+                    .collect();
+                rows.push(Row { file, outcomes });
+                continue;
+            }
+        };
+        rows.push(Row {
+            file,
+            outcomes: Vec::with_capacity(parts.len()),
+        });
+        for &part in parts {
+            if running.len() == workers {
+                finish(&mut rows, running.pop_front().unwrap());
+            }
+            let job = pool.submit(day, part, input.clone(), timeout);
+            running.push_back((rows.len() - 1, job));
+        }
+    }
+    for job in running {
+        finish(&mut rows, job);
+    }
+    rows
+}
+
+fn render(parts: &[Part], rows: &[Row]) -> String {
+    let mut header = vec!["file".to_string()];
+    for part in parts {
+        header.push(format!("part {}", part));
+        header.push("time".to_string());
+    }
+    header.push("status".to_string());
+
+    let mut table = vec![header];
+    for row in rows {
+        let mut cells = vec![row.file.clone()];
+        for outcome in &row.outcomes {
+            let (answer, time) = match outcome {
+                Outcome::Solved(answer, elapsed) => (answer.clone(), format!("{:.2?}", elapsed)),
+                Outcome::Failed(e) => (format!("error: {:#}", e), "-".to_string()),
+                Outcome::TimedOut => ("timed out".to_string(), "-".to_string()),
+                Outcome::Panicked => ("panicked".to_string(), "-".to_string()),
+                Outcome::Busy => ("busy".to_string(), "-".to_string()),
+                Outcome::NotRun => ("not run".to_string(), "-".to_string()),
+            };
+            cells.push(answer);
+            cells.push(time);
+        }
+        cells.push(if row.flagged() { "FLAGGED" } else { "ok" }.to_string());
+        table.push(cells);
+    }
+
+    table::render(&table)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use std::fs;
+
+    #[test]
+    fn test_solve_files() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("alice.txt"), "(()(()(")?;
+        fs::write(dir.path().join("bob.txt"), "))(")?;
+        fs::write(dir.path().join("carol.txt"), [0xff, 0xfe])?;
+
+        let day = aoc2015::find(1).unwrap();
+        let files = input_files(dir.path())?;
+        let rows = solve_files(day, &[Part::One], &files, Duration::from_secs(5));
+
+        let names: Vec<&str> = rows.iter().map(|row| row.file.as_str()).collect();
+        assert_eq!(names, vec!["alice.txt", "bob.txt", "carol.txt"]);
+        assert!(matches!(&rows[0].outcomes[0], Outcome::Solved(answer, _) if answer == "3"));
+        assert!(matches!(&rows[1].outcomes[0], Outcome::Solved(answer, _) if answer == "-1"));
+        assert!(rows[2].flagged());
+
+        // A panicking solver is a flagged row, not the end of the batch
+        let day = Day {
+            solve: |_, input| match input {
+                "))(" => panic!("solver bug"),
+                _ => Ok(input.len().to_string()),
+            },
+            ..day
+        };
+        let rows = solve_files(day, &[Part::One, Part::Two], &files, Duration::from_secs(5));
+        assert!(matches!(
+            &rows[0].outcomes[..],
+            [Outcome::Solved(..), Outcome::Solved(..)]
+        ));
+        assert!(matches!(
+            &rows[1].outcomes[..],
+            [Outcome::Panicked, Outcome::Panicked]
+        ));
+        assert!(matches!(
+            &rows[2].outcomes[..],
+            [Outcome::Failed(_), Outcome::Failed(_)]
+        ));
+        Ok(())
+    }
+
+    // Files queued behind a stuck worker are reported as not run, and the batch still ends
+    #[test]
+    fn test_stuck_solver() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let workers = worker::parallelism();
+        for i in 0..workers + 2 {
+            fs::write(dir.path().join(format!("{}.txt", i)), "(")?;
+        }
+
+        let day = Day {
+            solve: |_, _| loop {
+                std::thread::park();
+            },
+            ..aoc2015::find(1).unwrap()
+        };
+        let files = input_files(dir.path())?;
+        let rows = solve_files(day, &[Part::One], &files, Duration::from_millis(100));
+
+        let outcomes: Vec<&Outcome> = rows.iter().map(|row| &row.outcomes[0]).collect();
+        assert!(outcomes
+            .iter()
+            .all(|outcome| matches!(outcome, Outcome::TimedOut | Outcome::NotRun)));
+        // Each worker can only get stuck once
+        let timed_out = outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome, Outcome::TimedOut))
+            .count();
+        assert!(timed_out <= workers);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render() {
+        let rows = vec![
+            Row {
+                file: "alice.txt".to_string(),
+                outcomes: vec![
+                    Outcome::Solved("280".to_string(), Duration::from_millis(2)),
+                    Outcome::TimedOut,
+                ],
+            },
+            Row {
+                file: "bob.txt".to_string(),
+                outcomes: vec![
+                    Outcome::Solved("-1".to_string(), Duration::from_micros(15)),
+                    Outcome::Solved("1".to_string(), Duration::from_micros(3)),
+                ],
+            },
+        ];
+
+        let expected = indoc! {"
+            file       part 1  time     part 2     time    status
+            alice.txt  280     2.00ms   timed out  -       FLAGGED
+            bob.txt    -1      15.00µs  1          3.00µs  ok
+        "};
+        assert_eq!(render(&[Part::One, Part::Two], &rows), expected);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::table;
+use anyhow::{anyhow, Context, Result};
+use aoc2015::{Day, Part};
+use clap::Args;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Args)]
+pub struct Config {
+    /// TOML file with the median time allowed per part
+    #[arg(long, default_value = "budgets.toml")]
+    pub config: PathBuf,
+
+    /// Number of times each part is solved
+    #[arg(long, default_value_t = 5)]
+    pub runs: usize,
+
+    /// Directory holding the `dayNN.txt` input files, embedded or resolved when omitted
+    #[arg(long)]
+    pub inputs: Option<PathBuf>,
+}
+
+// Thresholds in milliseconds, with per-day overrides of the default
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Budgets {
+    default_ms: f64,
+    #[serde(default)]
+    days: BTreeMap<String, DayBudget>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayBudget {
+    part1_ms: Option<f64>,
+    part2_ms: Option<f64>,
+}
+
+impl Budgets {
+    fn load(path: &Path) -> Result<Budgets> {
+        let text = read_to_string(path)
+            .with_context(|| format!("Could not read budgets {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("Invalid budgets in {}", path.display()))
+    }
+
+    fn budget(&self, day: &str, part: Part) -> Duration {
+        let day = self.days.get(day);
+        let ms = match part {
+            Part::One => day.and_then(|day| day.part1_ms),
+            Part::Two => day.and_then(|day| day.part2_ms),
+        };
+        Duration::from_secs_f64(ms.unwrap_or(self.default_ms) / 1000.0)
+    }
+}
+
+struct Measurement {
+    day: &'static str,
+    part: Part,
+    median: Duration,
+    budget: Duration,
+}
+
+impl Measurement {
+    fn over_budget(&self) -> bool {
+        self.median > self.budget
+    }
+}
+
+pub fn check(config: Config) -> Result<()> {
+    if config.runs == 0 {
+        return Err(anyhow!("--runs must be at least 1"));
+    }
+    // Debug builds are an order of magnitude slower, every day would be over budget
+    if cfg!(debug_assertions) {
+        return Err(anyhow!(
+            "Budgets only hold for release builds, run `cargo run --release -p aoc -- budget`"
+        ));
+    }
+
+    let budgets = Budgets::load(&config.config)?;
+    let mut measurements = Vec::new();
+    for day in aoc2015::days() {
+        let input = crate::read_day_input(config.inputs.as_deref(), &day)?;
+        for part in [Part::One, Part::Two] {
+            measurements.push(Measurement {
+                day: day.name,
+                part,
+                median: median_time(day, part, &input, config.runs)?,
+                budget: budgets.budget(day.name, part),
+            });
+        }
+    }
+    print!("{}", render(&measurements));
+
+    let over = over_budget_days(&measurements);
+    if !over.is_empty() {
+        return Err(anyhow!("Over budget: {}", over.join(", ")));
+    }
+    Ok(())
+}
+
+// Parse and solve from scratch every run, as the runner does
+fn median_time(day: Day, part: Part, input: &str, runs: usize) -> Result<Duration> {
+    let mut times = Vec::with_capacity(runs);
+    for _ in 0..runs {
+        let start = Instant::now();
+        (day.solve)(part, input)?;
+        times.push(start.elapsed());
+    }
+    Ok(median(times))
+}
+
+fn median(mut times: Vec<Duration>) -> Duration {
+    times.sort();
+    let middle = times.len() / 2;
+    if times.len().is_multiple_of(2) {
+        (times[middle - 1] + times[middle]) / 2
+    } else {
+        times[middle]
+    }
+}
+
+fn over_budget_days(measurements: &[Measurement]) -> Vec<&'static str> {
+    let mut days: Vec<&'static str> = measurements
+        .iter()
+        .filter(|measurement| measurement.over_budget())
+        .map(|measurement| measurement.day)
+        .collect();
+    days.dedup();
+    days
+}
+
+fn render(measurements: &[Measurement]) -> String {
+    let mut rows = vec![vec![
+        "day".to_string(),
+        "part".to_string(),
+        "median".to_string(),
+        "budget".to_string(),
+        "status".to_string(),
+    ]];
+    for measurement in measurements {
+        let status = if measurement.over_budget() {
+            "OVER"
+        } else {
+            "ok"
+        };
+        rows.push(vec![
+            measurement.day.to_string(),
+            measurement.part.to_string(),
+            format!("{:.2?}", measurement.median),
+            format!("{:.2?}", measurement.budget),
+            status.to_string(),
+        ]);
+    }
+    let total: Duration = measurements
+        .iter()
+        .map(|measurement| measurement.median)
+        .sum();
+    rows.push(vec![
+        "total".to_string(),
+        String::new(),
+        format!("{:.2?}", total),
+    ]);
+    table::render(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_budgets() -> Result<()> {
+        let budgets: Budgets = toml::from_str(indoc! {"
+            default_ms = 50
+
+            [days]
+            day04 = { part1_ms = 200, part2_ms = 800 }
+            day10 = { part2_ms = 120.5 }
+        "})?;
+
+        assert_eq!(
+            budgets.budget("day01", Part::One),
+            Duration::from_millis(50)
+        );
+        assert_eq!(
+            budgets.budget("day04", Part::Two),
+            Duration::from_millis(800)
+        );
+        assert_eq!(
+            budgets.budget("day10", Part::One),
+            Duration::from_millis(50)
+        );
+        assert_eq!(
+            budgets.budget("day10", Part::Two),
+            Duration::from_micros(120_500)
+        );
+
+        assert!(toml::from_str::<Budgets>("default_ms = 50\nday04 = 1").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_shipped_budgets() -> Result<()> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../budgets.toml");
+        let budgets = Budgets::load(&path)?;
+        let total: Duration = aoc2015::days()
+            .flat_map(|day| [Part::One, Part::Two].map(|part| budgets.budget(day.name, part)))
+            .sum();
+        assert!(total <= Duration::from_millis(750), "{:?}", total);
+        Ok(())
+    }
+
+    #[test]
+    fn test_median() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(vec![ms(9), ms(1), ms(5)]), ms(5));
+        assert_eq!(median(vec![ms(4), ms(1), ms(100), ms(2)]), ms(3));
+        assert_eq!(median(vec![ms(7)]), ms(7));
+    }
+
+    #[test]
+    fn test_over_budget_days() {
+        let ms = Duration::from_millis;
+        let measurement = |day, part, median| Measurement {
+            day,
+            part,
+            median,
+            budget: ms(50),
+        };
+        let measurements = vec![
+            measurement("day01", Part::One, ms(1)),
+            measurement("day04", Part::One, ms(60)),
+            measurement("day04", Part::Two, ms(900)),
+            measurement("day09", Part::Two, ms(50)),
+            measurement("day10", Part::Two, ms(51)),
+        ];
+        assert_eq!(over_budget_days(&measurements), vec!["day04", "day10"]);
+    }
+}
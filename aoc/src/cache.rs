@@ -0,0 +1,123 @@
+use anyhow::{anyhow, Context, Result};
+use aoc2015::{Day, Part};
+use md5::{Digest, Md5};
+use std::fs::{self, read_dir, read_to_string};
+use std::path::PathBuf;
+
+include!(concat!(env!("OUT_DIR"), "/solver_versions.rs"));
+
+// Identifies an answer: which solver produced it and from which input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Key {
+    day: String,
+    part: Part,
+    version: String,
+    input_hash: String,
+}
+
+impl Key {
+    pub fn new(day: &Day, part: Part, input: &str) -> Result<Key> {
+        let version = solver_version(day.name)
+            .ok_or_else(|| anyhow!("No solver version for {}", day.name))?;
+        Ok(Key {
+            day: day.name.to_string(),
+            part,
+            version: version.to_string(),
+            input_hash: format!("{:x}", Md5::digest(input.as_bytes())),
+        })
+    }
+
+    fn prefix(&self) -> String {
+        format!("{}-part{}-", self.day, self.part)
+    }
+
+    fn file_name(&self) -> String {
+        format!("{}{}-{}.txt", self.prefix(), self.version, self.input_hash)
+    }
+}
+
+// Answers stored on disk, one small file per key
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Cache {
+        Cache { dir: dir.into() }
+    }
+
+    pub fn get(&self, key: &Key) -> Option<String> {
+        read_to_string(self.dir.join(key.file_name())).ok()
+    }
+
+    pub fn put(&self, key: &Key, answer: &str) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Could not create cache directory {}", self.dir.display()))?;
+        self.evict_stale(key)?;
+        fs::write(self.dir.join(key.file_name()), answer).context("Could not write cached answer")
+    }
+
+    // Entries for the same day and part made by an older solver can never be hit again
+    fn evict_stale(&self, key: &Key) -> Result<()> {
+        let prefix = key.prefix();
+        let current = format!("{}{}-", prefix, key.version);
+        for entry in read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with(&prefix) && !name.starts_with(&current) {
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key() {
+        let day = aoc2015::find(1).unwrap();
+        let key = Key::new(&day, Part::One, "(()").unwrap();
+        assert_eq!(key, Key::new(&day, Part::One, "(()").unwrap());
+        assert_ne!(key, Key::new(&day, Part::Two, "(()").unwrap());
+        assert_ne!(key, Key::new(&day, Part::One, "())").unwrap());
+        assert!(key.file_name().starts_with("day01-part1-"));
+    }
+
+    #[test]
+    fn test_get_put() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = Cache::new(dir.path());
+        let day = aoc2015::find(1).unwrap();
+        let key = Key::new(&day, Part::One, "(()")?;
+
+        assert_eq!(cache.get(&key), None);
+        cache.put(&key, "1")?;
+        assert_eq!(cache.get(&key), Some("1".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_stale_versions_are_evicted() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = Cache::new(dir.path());
+        let day = aoc2015::find(1).unwrap();
+        let key = Key::new(&day, Part::One, "(()")?;
+        let old_key = Key {
+            version: "0000000000000000".to_string(),
+            ..key.clone()
+        };
+        let other_part = Key::new(&day, Part::Two, "(()")?;
+
+        cache.put(&old_key, "41")?;
+        cache.put(&other_part, "3")?;
+        cache.put(&key, "42")?;
+
+        assert_eq!(cache.get(&old_key), None);
+        assert_eq!(cache.get(&other_part), Some("3".to_string()));
+        assert_eq!(cache.get(&key), Some("42".to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+mod batch;
+mod budget;
+mod cache;
+mod readme;
+mod serve;
+mod table;
+mod worker;
+
+use anyhow::{anyhow, Context, Result};
+use aoc2015::{Day, Part, Tag};
+use cache::{Cache, Key};
+use clap::{Parser, Subcommand};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2015 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or every day, from its puzzle input
+    Run {
+        /// Day to solve, all days when omitted
+        #[arg(short, long)]
+        day: Option<u8>,
+
+        /// Part to solve, both parts when omitted
+        #[arg(short, long)]
+        part: Option<u8>,
+
+        /// Directory holding the `dayNN.txt` input files, embedded or resolved when omitted
+        #[arg(long)]
+        inputs: Option<PathBuf>,
+
+        /// Always solve, ignoring and not updating cached answers
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Directory where answers are cached between runs
+        #[arg(long, default_value = ".aoc-cache")]
+        cache_dir: PathBuf,
+    },
+    /// Walk through how a day's answers are reached
+    Explain {
+        /// Day to explain
+        #[arg(short, long)]
+        day: u8,
+
+        /// Part to explain, both parts when omitted
+        #[arg(short, long)]
+        part: Option<u8>,
+
+        /// Directory holding the `dayNN.txt` input files, embedded or resolved when omitted
+        #[arg(long)]
+        inputs: Option<PathBuf>,
+    },
+    /// Solve one day against every input file in a directory
+    Batch(batch::Config),
+    /// Check every day's median solve time against the budgets file
+    Budget(budget::Config),
+    /// List the solved days with their puzzle details
+    List,
+    /// Rewrite the README's progress table
+    Readme(readme::Config),
+    /// Expose the solvers over a local HTTP API
+    Serve(serve::Config),
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Run {
+            day,
+            part,
+            inputs,
+            no_cache,
+            cache_dir,
+        } => {
+            let cache = (!no_cache).then(|| Cache::new(cache_dir));
+            run(day, part, inputs, cache.as_ref())
+        }
+        Command::Explain { day, part, inputs } => explain(day, part, inputs),
+        Command::Batch(config) => batch::batch(config),
+        Command::Budget(config) => budget::check(config),
+        Command::List => {
+            print!("{}", list());
+            Ok(())
+        }
+        Command::Readme(config) => readme::generate(config),
+        Command::Serve(config) => serve::serve(config),
+    }
+}
+
+fn run(
+    day: Option<u8>,
+    part: Option<u8>,
+    inputs: Option<PathBuf>,
+    cache: Option<&Cache>,
+) -> Result<()> {
+    let days: Vec<Day> = match day {
+        Some(number) => {
+            vec![aoc2015::find(number).ok_or_else(|| anyhow!("Unknown day: {}", number))?]
+        }
+        None => aoc2015::days().collect(),
+    };
+    let parts = select_parts(part)?;
+    let inputs = inputs.as_deref();
+
+    // Sequentially each day is reported as soon as it's solved, in parallel once all are done
+    #[cfg(not(feature = "parallel"))]
+    let reports = days.iter().map(|day| solve_day(day, &parts, inputs, cache));
+    #[cfg(feature = "parallel")]
+    let reports: Vec<Result<Vec<String>>> = days
+        .par_iter()
+        .map(|day| solve_day(day, &parts, inputs, cache))
+        .collect();
+
+    for report in reports {
+        for line in report? {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+// Solve the selected parts of a day, one report line per part
+fn solve_day(
+    day: &Day,
+    parts: &[Part],
+    inputs: Option<&Path>,
+    cache: Option<&Cache>,
+) -> Result<Vec<String>> {
+    let input = read_day_input(inputs, day)?;
+    let mut lines = vec![format!("Day {}: {}", day.number, day.metadata.title)];
+
+    for &part in parts {
+        let key = Key::new(day, part, &input)?;
+        if let Some(answer) = cache.and_then(|cache| cache.get(&key)) {
+            lines.push(format!("{}: {} (cached)", part_label(day, part), answer));
+            continue;
+        }
+
+        let start = Instant::now();
+        let answer = (day.solve)(part, &input)?;
+        lines.push(format!(
+            "{}: {} ({:.2?})",
+            part_label(day, part),
+            answer,
+            start.elapsed()
+        ));
+
+        if let Some(cache) = cache {
+            cache.put(&key, &answer)?;
+        }
+    }
+    Ok(lines)
+}
+
+fn explain(day: u8, part: Option<u8>, inputs: Option<PathBuf>) -> Result<()> {
+    let day = aoc2015::find(day).ok_or_else(|| anyhow!("Unknown day: {}", day))?;
+    let explain = day
+        .explain
+        .ok_or_else(|| anyhow!("{} has no explanation", day.name))?;
+    let input = read_day_input(inputs.as_deref(), &day)?;
+
+    for part in select_parts(part)? {
+        println!("{} part {}:", day.name, part);
+        println!("{}", explain(part, &input)?);
+    }
+    Ok(())
+}
+
+// "  part 1, final floor"
+fn part_label(day: &Day, part: Part) -> String {
+    format!("  part {}, {}", part, day.metadata.answer(part).label)
+}
+
+// Index of the solved days, one row per day
+fn list() -> String {
+    let mut rows = vec![vec![
+        "day".to_string(),
+        "title".to_string(),
+        "tags".to_string(),
+        "url".to_string(),
+    ]];
+    for day in aoc2015::days() {
+        let metadata = day.metadata;
+        let tags: Vec<String> = metadata.tags.iter().map(Tag::to_string).collect();
+        rows.push(vec![
+            day.number.to_string(),
+            metadata.title.to_string(),
+            tags.join(", "),
+            metadata.url(),
+        ]);
+    }
+    table::render(&rows)
+}
+
+fn select_parts(part: Option<u8>) -> Result<Vec<Part>> {
+    Ok(match part {
+        Some(number) => vec![Part::try_from(number)?],
+        None => vec![Part::One, Part::Two],
+    })
+}
+
+// An explicit inputs directory wins over the embedded input, which wins over resolving one
+fn read_day_input(inputs: Option<&Path>, day: &Day) -> Result<String> {
+    let input_path = match (inputs, day.input) {
+        (Some(inputs), _) => inputs.join(format!("{}.txt", day.name)),
+        (None, Some(input)) => return Ok(input.to_string()),
+        (None, None) => aoc2015::resolve_input_path(day.name)?,
+    };
+    read_to_string(&input_path)
+        .with_context(|| format!("Could not read input file {}", input_path.display()))
+}
@@ -0,0 +1,322 @@
+use anyhow::{anyhow, Context, Result};
+use aoc2015::{display_name, Day, Part, Tag};
+use clap::Args;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::{self, Display};
+use std::fs::{self, read_to_string};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const START_MARKER: &str = "<!-- progress:start -->";
+const END_MARKER: &str = "<!-- progress:end -->";
+
+#[derive(Debug, Clone, Args)]
+pub struct Config {
+    /// README whose progress section is rewritten
+    #[arg(long, default_value = "README.md")]
+    pub readme: PathBuf,
+
+    /// TOML file with the accepted answer of each day's parts
+    #[arg(long, default_value = "answers.toml")]
+    pub answers: PathBuf,
+
+    /// Directory holding the `dayNN.txt` input files, embedded or resolved when omitted
+    #[arg(long)]
+    pub inputs: Option<PathBuf>,
+
+    /// Where criterion keeps its latest benchmark results
+    #[arg(long, default_value = "target/criterion")]
+    pub criterion_dir: PathBuf,
+
+    /// Don't run each day's tests
+    #[arg(long)]
+    pub skip_tests: bool,
+}
+
+// Accepted answers, written as numbers or strings
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+enum Expected {
+    Integer(i64),
+    Text(String),
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Integer(answer) => write!(f, "{}", answer),
+            Expected::Text(answer) => f.write_str(answer),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<Expected>,
+    part2: Option<Expected>,
+}
+
+type Answers = BTreeMap<String, DayAnswers>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Verification {
+    Verified,
+    Wrong,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TestStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+struct Progress {
+    day: Day,
+    tests: TestStatus,
+    parts: [(Verification, Option<Duration>); 2],
+}
+
+pub fn generate(config: Config) -> Result<()> {
+    let answers: Answers = if config.answers.exists() {
+        let text = read_to_string(&config.answers)
+            .with_context(|| format!("Could not read {}", config.answers.display()))?;
+        toml::from_str(&text)
+            .with_context(|| format!("Invalid answers in {}", config.answers.display()))?
+    } else {
+        Answers::new()
+    };
+
+    let mut progress = Vec::new();
+    for day in aoc2015::days() {
+        let tests = if config.skip_tests {
+            TestStatus::Skipped
+        } else {
+            run_tests(&day)?
+        };
+        let expected = answers.get(day.name);
+        let parts = [Part::One, Part::Two].map(|part| {
+            let expected = expected.and_then(|answers| match part {
+                Part::One => answers.part1.as_ref(),
+                Part::Two => answers.part2.as_ref(),
+            });
+            (
+                verify(&day, part, config.inputs.as_deref(), expected),
+                bench_median(&config.criterion_dir, &day, part),
+            )
+        });
+        progress.push(Progress { day, tests, parts });
+    }
+
+    let readme = read_to_string(&config.readme)
+        .with_context(|| format!("Could not read {}", config.readme.display()))?;
+    let updated = replace_section(&readme, &render(&progress))?;
+    fs::write(&config.readme, updated)
+        .with_context(|| format!("Could not write {}", config.readme.display()))?;
+    println!("Updated progress in {}", config.readme.display());
+    Ok(())
+}
+
+fn run_tests(day: &Day) -> Result<TestStatus> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .args(["test", "--quiet", "-p", day.name])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .with_context(|| format!("Could not run the tests of {}", day.name))?;
+    Ok(if status.success() {
+        TestStatus::Passed
+    } else {
+        TestStatus::Failed
+    })
+}
+
+// A part is only verified when its input is around and the answer was accepted
+fn verify(
+    day: &Day,
+    part: Part,
+    inputs: Option<&Path>,
+    expected: Option<&Expected>,
+) -> Verification {
+    let Some(expected) = expected else {
+        return Verification::Unknown;
+    };
+    let Ok(input) = crate::read_day_input(inputs, day) else {
+        return Verification::Unknown;
+    };
+    match (day.solve)(part, &input) {
+        Ok(answer) if answer == expected.to_string() => Verification::Verified,
+        _ => Verification::Wrong,
+    }
+}
+
+// Median of the latest `parse + partN` benchmark, as written by criterion
+fn bench_median(criterion_dir: &Path, day: &Day, part: Part) -> Option<Duration> {
+    let estimates = criterion_dir
+        .join(format!("{} - full solution", display_name(day.name)))
+        .join(format!("parse + part{}", part))
+        .join("new/estimates.json");
+    let estimates: serde_json::Value =
+        serde_json::from_str(&read_to_string(estimates).ok()?).ok()?;
+    let nanos = estimates["median"]["point_estimate"].as_f64()?;
+    Some(Duration::from_secs_f64(nanos / 1e9))
+}
+
+fn render(progress: &[Progress]) -> String {
+    let mut lines = vec![
+        "| Day | Puzzle | Tags | Stars | Tests | Part 1 | Part 2 |".to_string(),
+        "|----:|--------|------|-------|:-----:|-------:|-------:|".to_string(),
+    ];
+    for entry in progress {
+        let metadata = entry.day.metadata;
+        let tags: Vec<String> = metadata.tags.iter().map(Tag::to_string).collect();
+        let stars: String = entry
+            .parts
+            .iter()
+            .map(|(verification, _)| match verification {
+                Verification::Verified => "⭐",
+                Verification::Wrong => "❌",
+                Verification::Unknown => "",
+            })
+            .collect();
+        let tests = match entry.tests {
+            TestStatus::Passed => "✅",
+            TestStatus::Failed => "❌",
+            TestStatus::Skipped => "–",
+        };
+        let [part1, part2] = entry.parts.map(|(_, median)| match median {
+            Some(median) => format!("{:.2?}", median),
+            None => "–".to_string(),
+        });
+
+        lines.push(format!(
+            "| {} | [{}]({}) | {} | {} | {} | {} | {} |",
+            metadata.day,
+            metadata.title,
+            metadata.url(),
+            tags.join(", "),
+            stars,
+            tests,
+            part1,
+            part2
+        ));
+    }
+    lines.join("\n")
+}
+
+// Swap whatever sits between the progress markers for `table`
+fn replace_section(readme: &str, table: &str) -> Result<String> {
+    let start = readme
+        .find(START_MARKER)
+        .ok_or_else(|| anyhow!("README has no {} marker", START_MARKER))?
+        + START_MARKER.len();
+    let end = readme[start..]
+        .find(END_MARKER)
+        .ok_or_else(|| anyhow!("README has no {} marker", END_MARKER))?
+        + start;
+
+    Ok(format!(
+        "{}\n{}\n{}",
+        &readme[..start],
+        table,
+        &readme[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_replace_section() -> Result<()> {
+        let readme = indoc! {"
+            # Title
+            <!-- progress:start -->
+            old table
+            <!-- progress:end -->
+            ## Next
+        "};
+        let expected = indoc! {"
+            # Title
+            <!-- progress:start -->
+            | new |
+            <!-- progress:end -->
+            ## Next
+        "};
+        assert_eq!(replace_section(readme, "| new |")?, expected);
+        assert_eq!(replace_section(expected, "| new |")?, expected);
+
+        assert!(replace_section("# Title\n", "| new |").is_err());
+        assert!(replace_section("<!-- progress:start -->\n", "| new |").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("day01.txt"), "(()(()(").unwrap();
+        let day = aoc2015::find(1).unwrap();
+        let inputs = Some(dir.path());
+
+        let right = Expected::Integer(3);
+        let wrong = Expected::Text("4".to_string());
+        assert_eq!(
+            verify(&day, Part::One, inputs, Some(&right)),
+            Verification::Verified
+        );
+        assert_eq!(
+            verify(&day, Part::One, inputs, Some(&wrong)),
+            Verification::Wrong
+        );
+        assert_eq!(verify(&day, Part::One, inputs, None), Verification::Unknown);
+
+        let day = aoc2015::find(2).unwrap();
+        assert_eq!(
+            verify(&day, Part::One, inputs, Some(&right)),
+            Verification::Unknown
+        );
+    }
+
+    #[test]
+    fn test_bench_median() {
+        let dir = tempfile::tempdir().unwrap();
+        let estimates = dir.path().join("day 01 - full solution/parse + part2/new");
+        fs::create_dir_all(&estimates).unwrap();
+        fs::write(
+            estimates.join("estimates.json"),
+            r#"{"median": {"point_estimate": 1500.0}}"#,
+        )
+        .unwrap();
+
+        let day = aoc2015::find(1).unwrap();
+        assert_eq!(
+            bench_median(dir.path(), &day, Part::Two),
+            Some(Duration::from_nanos(1500))
+        );
+        assert_eq!(bench_median(dir.path(), &day, Part::One), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let progress = vec![Progress {
+            day: aoc2015::find(1).unwrap(),
+            tests: TestStatus::Passed,
+            parts: [
+                (Verification::Verified, Some(Duration::from_micros(2))),
+                (Verification::Unknown, None),
+            ],
+        }];
+        let table = render(&progress);
+        assert_eq!(
+            table.lines().last().unwrap(),
+            "| 1 | [Not Quite Lisp](https://adventofcode.com/2015/day/1) | simulation, strings | ⭐ | ✅ | 2.00µs | – |"
+        );
+    }
+}
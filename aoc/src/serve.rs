@@ -0,0 +1,332 @@
+use crate::worker::{self, Outcome, Pool};
+use anyhow::{anyhow, Result};
+use aoc2015::{Day, Part};
+use clap::Args;
+use serde_json::{json, Value};
+use std::io::Read;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+#[derive(Debug, Clone, Args)]
+pub struct Config {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:3000")]
+    pub addr: String,
+
+    /// Largest accepted puzzle input, in bytes
+    #[arg(long, default_value_t = 1024 * 1024)]
+    pub max_body_bytes: usize,
+
+    /// Time allowed for a single solve before answering 504
+    #[arg(long, default_value_t = 30)]
+    pub timeout_secs: u64,
+
+    /// Solves waiting for a free worker before answering 503
+    #[arg(long, default_value_t = 16)]
+    pub max_queued: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiError {
+    status: u16,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, message: impl Into<String>) -> ApiError {
+        ApiError {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+pub fn serve(config: Config) -> Result<()> {
+    let server = Server::http(&config.addr)
+        .map_err(|e| anyhow!("Could not listen on {}: {}", config.addr, e))?;
+    println!("Listening on http://{}", config.addr);
+
+    let workers = worker::parallelism();
+    let pool = Arc::new(Pool::new(workers, config.max_queued));
+    // One connection per worker or queue slot, the rest wait to be accepted
+    let connections = workers + config.max_queued;
+    let server = Arc::new(server);
+    let config = Arc::new(config);
+    let threads: Vec<_> = (0..connections)
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let config = Arc::clone(&config);
+            let pool = Arc::clone(&pool);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    respond(request, &config, &pool);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        let _ = thread.join();
+    }
+    Ok(())
+}
+
+fn respond(mut request: Request, config: &Config, pool: &Pool) {
+    let (status, body) = match handle(&mut request, config, pool) {
+        Ok(body) => (200, body),
+        Err(e) => (e.status, json!({ "error": e.message })),
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        eprintln!("Error sending response: {}", e);
+    }
+}
+
+fn handle(request: &mut Request, config: &Config, pool: &Pool) -> Result<Value, ApiError> {
+    let (day, part) = route(request.method(), request.url())?;
+    let input = read_body(request, config.max_body_bytes)?;
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let (answer, elapsed) = solve_with_timeout(pool, day, part, input, timeout)?;
+
+    Ok(json!({
+        "day": day.number,
+        "part": part.number(),
+        "answer": answer,
+        "elapsed_ms": elapsed.as_secs_f64() * 1000.0,
+    }))
+}
+
+// Match `POST /days/{n}/parts/{p}`
+fn route(method: &Method, url: &str) -> Result<(Day, Part), ApiError> {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let (day, part) = match segments.as_slice() {
+        ["days", day, "parts", part] => (day, part),
+        _ => return Err(ApiError::new(404, format!("No route for {}", path))),
+    };
+
+    if *method != Method::Post {
+        return Err(ApiError::new(405, "Only POST is supported"));
+    }
+
+    let day = day
+        .parse::<u8>()
+        .ok()
+        .and_then(aoc2015::find)
+        .ok_or_else(|| ApiError::new(404, format!("Unknown day: {}", day)))?;
+    let part = part
+        .parse::<u8>()
+        .ok()
+        .and_then(|p| Part::try_from(p).ok())
+        .ok_or_else(|| ApiError::new(404, format!("Unknown part: {}", part)))?;
+
+    Ok((day, part))
+}
+
+fn read_body(request: &mut Request, max_bytes: usize) -> Result<String, ApiError> {
+    let too_large = || ApiError::new(413, format!("Input exceeds {} bytes", max_bytes));
+
+    if request.body_length().is_some_and(|len| len > max_bytes) {
+        return Err(too_large());
+    }
+
+    // Content-Length may be missing (chunked uploads), so cap the read as well
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| ApiError::new(400, format!("Could not read body: {}", e)))?;
+    if body.len() > max_bytes {
+        return Err(too_large());
+    }
+
+    String::from_utf8(body).map_err(|_| ApiError::new(400, "Input is not valid UTF-8"))
+}
+
+// Solve on the worker pool so a slow day can't hold the connection forever
+fn solve_with_timeout(
+    pool: &Pool,
+    day: Day,
+    part: Part,
+    input: String,
+    timeout: Duration,
+) -> Result<(String, Duration), ApiError> {
+    match worker::solve_with_timeout(pool, day, part, input, timeout) {
+        Outcome::Solved(answer, elapsed) => Ok((answer, elapsed)),
+        Outcome::Failed(e) => Err(ApiError::new(422, format!("{:#}", e))),
+        Outcome::TimedOut => Err(ApiError::new(
+            504,
+            format!("Solver exceeded {}s", timeout.as_secs_f64()),
+        )),
+        Outcome::Panicked => Err(ApiError::new(500, "Solver panicked")),
+        Outcome::Busy | Outcome::NotRun => {
+            Err(ApiError::new(503, "All workers are busy, try again later"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slow_solve(_part: Part, _input: &str) -> Result<String> {
+        thread::sleep(Duration::from_millis(500));
+        Ok("late".to_string())
+    }
+
+    fn failing_solve(_part: Part, _input: &str) -> Result<String> {
+        Err(anyhow!("bad input"))
+    }
+
+    fn panicking_solve(_part: Part, _input: &str) -> Result<String> {
+        panic!("solver bug")
+    }
+
+    const SLOW: Day = Day {
+        number: 99,
+        name: "slow",
+        solve: slow_solve,
+        explain: None,
+        input: None,
+        metadata: aoc2015::day01::METADATA,
+    };
+
+    const FAILING: Day = Day {
+        number: 98,
+        name: "failing",
+        solve: failing_solve,
+        explain: None,
+        input: None,
+        metadata: aoc2015::day01::METADATA,
+    };
+
+    const PANICKING: Day = Day {
+        number: 97,
+        name: "panicking",
+        solve: panicking_solve,
+        explain: None,
+        input: None,
+        metadata: aoc2015::day01::METADATA,
+    };
+
+    #[test]
+    fn test_route() {
+        let (day, part) = route(&Method::Post, "/days/7/parts/2").unwrap();
+        assert_eq!(day.number, 7);
+        assert_eq!(part, Part::Two);
+
+        let (day, _) = route(&Method::Post, "/days/01/parts/1?verbose").unwrap();
+        assert_eq!(day.number, 1);
+
+        assert_eq!(
+            route(&Method::Get, "/days/7/parts/2").unwrap_err().status,
+            405
+        );
+        assert_eq!(
+            route(&Method::Post, "/days/42/parts/1").unwrap_err().status,
+            404
+        );
+        assert_eq!(
+            route(&Method::Post, "/days/7/parts/3").unwrap_err().status,
+            404
+        );
+        assert_eq!(route(&Method::Post, "/days/7").unwrap_err().status, 404);
+    }
+
+    #[test]
+    fn test_solve_with_timeout() {
+        let pool = Pool::new(2, 4);
+        let day = aoc2015::find(1).unwrap();
+        let (answer, _) = solve_with_timeout(
+            &pool,
+            day,
+            Part::One,
+            "(()(()(".to_string(),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        assert_eq!(answer, "3");
+
+        let err = solve_with_timeout(
+            &pool,
+            SLOW,
+            Part::One,
+            String::new(),
+            Duration::from_millis(10),
+        )
+        .unwrap_err();
+        assert_eq!(err.status, 504);
+
+        let err = solve_with_timeout(
+            &pool,
+            FAILING,
+            Part::One,
+            String::new(),
+            Duration::from_secs(5),
+        )
+        .unwrap_err();
+        assert_eq!(err, ApiError::new(422, "bad input"));
+
+        let day = aoc2015::find(3).unwrap();
+        let err = solve_with_timeout(
+            &pool,
+            day,
+            Part::One,
+            "^x\n".to_string(),
+            Duration::from_secs(5),
+        )
+        .unwrap_err();
+        assert_eq!(err.status, 422);
+
+        // A megabyte of seed is turned away before the rounds run out of memory
+        let day = aoc2015::find(10).unwrap();
+        let err = solve_with_timeout(
+            &pool,
+            day,
+            Part::Two,
+            "1".repeat(1024 * 1024),
+            Duration::from_secs(5),
+        )
+        .unwrap_err();
+        assert_eq!(err.status, 422);
+    }
+
+    #[test]
+    fn test_pool() {
+        let pool = Pool::new(1, 1);
+        let timeout = Duration::from_secs(5);
+        let err =
+            solve_with_timeout(&pool, PANICKING, Part::One, String::new(), timeout).unwrap_err();
+        assert_eq!(err, ApiError::new(500, "Solver panicked"));
+
+        // The worker survives the panic
+        let day = aoc2015::find(1).unwrap();
+        let (answer, _) =
+            solve_with_timeout(&pool, day, Part::One, "(".to_string(), timeout).unwrap();
+        assert_eq!(answer, "1");
+
+        // One running and one queued, the rest are turned away
+        let jobs: Vec<_> = (0..3)
+            .map(|_| pool.submit(SLOW, Part::One, "", timeout))
+            .collect();
+        assert!(jobs.iter().any(Option::is_none));
+
+        // The second solve only starts once the first is done, but gets its full timeout
+        let pool = Pool::new(1, 2);
+        let timeout = Duration::from_millis(800);
+        let first = pool.submit(SLOW, Part::One, "", timeout).unwrap();
+        let second = pool.submit(SLOW, Part::One, "", timeout).unwrap();
+        assert!(matches!(first.wait(), Outcome::Solved(..)));
+        assert!(matches!(second.wait(), Outcome::Solved(..)));
+
+        let closed = Pool::new(0, 0);
+        let err = solve_with_timeout(&closed, day, Part::One, String::new(), timeout).unwrap_err();
+        assert_eq!(err.status, 503);
+    }
+}
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use aoc2015::{Day, Part};
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How a solve on a worker thread ended
+#[derive(Debug)]
+pub enum Outcome {
+    Solved(String, Duration),
+    Failed(anyhow::Error),
+    TimedOut,
+    Panicked,
+    Busy,
+    NotRun,
+}
+
+type Task = Box<dyn FnOnce() + Send>;
+
+enum Message {
+    Started,
+    Finished(Result<String>, Duration),
+}
+
+// One worker per core unless the platform can't tell
+pub fn parallelism() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+// A fixed set of worker threads fed from a bounded queue
+pub struct Pool {
+    sender: SyncSender<Task>,
+}
+
+impl Pool {
+    pub fn new(workers: usize, queue: usize) -> Pool {
+        let (sender, receiver) = mpsc::sync_channel::<Task>(queue);
+        let receiver = Arc::new(Mutex::new(receiver));
+        for _ in 0..workers {
+            let receiver = Arc::clone(&receiver);
+            thread::spawn(move || loop {
+                let task = receiver.lock().unwrap().recv();
+                let Ok(task) = task else {
+                    break;
+                };
+                // The job's sender is dropped while unwinding, which its waiter sees as a panic
+                let _ = panic::catch_unwind(AssertUnwindSafe(task));
+            });
+        }
+        Pool { sender }
+    }
+
+    // Queue a solve, or None when every worker is taken and the queue is full
+    pub fn submit(
+        &self,
+        day: Day,
+        part: Part,
+        input: impl AsRef<str> + Send + 'static,
+        timeout: Duration,
+    ) -> Option<Job> {
+        let (sender, receiver) = mpsc::channel::<Message>();
+        let task: Task = Box::new(move || {
+            // Nobody is waiting for a job that gave up before it started
+            if sender.send(Message::Started).is_err() {
+                return;
+            }
+            let start = Instant::now();
+            let result = (day.solve)(part, input.as_ref());
+            let _ = sender.send(Message::Finished(result, start.elapsed()));
+        });
+        self.sender.try_send(task).ok()?;
+        Some(Job { receiver, timeout })
+    }
+}
+
+// A queued or running solve, timed from when a worker picks it up
+pub struct Job {
+    receiver: Receiver<Message>,
+    timeout: Duration,
+}
+
+impl Job {
+    // Threads can't be cancelled, so a timed out solver keeps its worker until it returns.
+    // Waiting for a free worker is bounded by the same timeout, after which the job is not run
+    pub fn wait(self) -> Outcome {
+        match self.receiver.recv_timeout(self.timeout) {
+            Ok(Message::Started) => {}
+            Ok(Message::Finished(..)) | Err(RecvTimeoutError::Timeout) => return Outcome::NotRun,
+            Err(RecvTimeoutError::Disconnected) => return Outcome::Panicked,
+        }
+        match self.receiver.recv_timeout(self.timeout) {
+            Ok(Message::Finished(Ok(answer), elapsed)) => Outcome::Solved(answer, elapsed),
+            Ok(Message::Finished(Err(e), _)) => Outcome::Failed(e),
+            Ok(Message::Started) | Err(RecvTimeoutError::Timeout) => Outcome::TimedOut,
+            Err(RecvTimeoutError::Disconnected) => Outcome::Panicked,
+        }
+    }
+}
+
+pub fn solve_with_timeout(
+    pool: &Pool,
+    day: Day,
+    part: Part,
+    input: String,
+    timeout: Duration,
+) -> Outcome {
+    pool.submit(day, part, input, timeout)
+        .map_or(Outcome::Busy, Job::wait)
+}
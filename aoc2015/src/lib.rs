@@ -0,0 +1,139 @@
+//! Every Advent of Code 2015 solution behind a single crate.
+//!
+//! Each day is re-exported as a module (`aoc2015::day07::Circuit`) and gated by a
+//! cargo feature of the same name, so only the dependencies of enabled days are built.
+
+use anyhow::Result;
+pub use aoc_common::{
+    display_name, resolve_input_path, Answer, AnswerKind, Explain, Metadata, Part, Solution, Tag,
+};
+
+#[cfg(feature = "day01")]
+pub use day01;
+#[cfg(feature = "day02")]
+pub use day02;
+#[cfg(feature = "day03")]
+pub use day03;
+#[cfg(feature = "day04")]
+pub use day04;
+#[cfg(feature = "day05")]
+pub use day05;
+#[cfg(feature = "day06")]
+pub use day06;
+#[cfg(feature = "day07")]
+pub use day07;
+#[cfg(feature = "day08")]
+pub use day08;
+#[cfg(feature = "day09")]
+pub use day09;
+#[cfg(feature = "day10")]
+pub use day10;
+#[cfg(feature = "day11")]
+pub use day11;
+
+// A solved day, type-erased so every day can be driven the same way
+#[derive(Debug, Clone, Copy)]
+pub struct Day {
+    pub number: u8,
+    pub name: &'static str,
+    pub solve: fn(Part, &str) -> Result<String>,
+    pub explain: Option<fn(Part, &str) -> Result<String>>,
+    pub input: Option<&'static str>,
+    pub metadata: Metadata,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(number: u8, name: &'static str) -> Day {
+        Day {
+            number,
+            name,
+            solve: aoc_common::solve::<S>,
+            explain: None,
+            input: S::INPUT,
+            metadata: S::METADATA,
+        }
+    }
+
+    pub const fn with_explain<S: Explain>(self) -> Day {
+        Day {
+            explain: Some(aoc_common::explain::<S>),
+            ..self
+        }
+    }
+}
+
+// Look up an enabled day by its number
+pub fn find(number: u8) -> Option<Day> {
+    match number {
+        #[cfg(feature = "day01")]
+        1 => Some(Day::new::<day01::Day01>(1, "day01").with_explain::<day01::Day01>()),
+        #[cfg(feature = "day02")]
+        2 => Some(Day::new::<day02::Day02>(2, "day02")),
+        #[cfg(feature = "day03")]
+        3 => Some(Day::new::<day03::Day03>(3, "day03")),
+        #[cfg(feature = "day04")]
+        4 => Some(Day::new::<day04::Day04>(4, "day04")),
+        #[cfg(feature = "day05")]
+        5 => Some(Day::new::<day05::Day05>(5, "day05").with_explain::<day05::Day05>()),
+        #[cfg(feature = "day06")]
+        6 => Some(Day::new::<day06::Day06>(6, "day06")),
+        #[cfg(feature = "day07")]
+        7 => Some(Day::new::<day07::Day07>(7, "day07").with_explain::<day07::Day07>()),
+        #[cfg(feature = "day08")]
+        8 => Some(Day::new::<day08::Day08>(8, "day08")),
+        #[cfg(feature = "day09")]
+        9 => Some(Day::new::<day09::Day09>(9, "day09").with_explain::<day09::Day09>()),
+        #[cfg(feature = "day10")]
+        10 => Some(Day::new::<day10::Day10>(10, "day10")),
+        #[cfg(feature = "day11")]
+        11 => Some(Day::new::<day11::Day11>(11, "day11").with_explain::<day11::Day11>()),
+        _ => None,
+    }
+}
+
+// Every enabled day, in order
+pub fn days() -> impl Iterator<Item = Day> {
+    (1..=25).filter_map(find)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let day = find(7).unwrap();
+        assert_eq!(day.number, 7);
+        assert_eq!(day.name, "day07");
+        assert!(find(0).is_none());
+        assert!(find(26).is_none());
+    }
+
+    #[test]
+    fn test_days_in_order() {
+        let numbers: Vec<u8> = days().map(|day| day.number).collect();
+        assert_eq!(numbers, (1..=11).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_metadata() {
+        for day in days() {
+            assert_eq!(day.metadata.day, day.number);
+            assert!(!day.metadata.tags.is_empty());
+        }
+        assert_eq!(find(7).unwrap().metadata.title, "Some Assembly Required");
+    }
+
+    #[test]
+    fn test_explain() {
+        let explain = find(1).unwrap().explain.unwrap();
+        assert!(explain(Part::Two, "())").unwrap().contains("step 3"));
+        assert!(find(2).unwrap().explain.is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        let day = find(1).unwrap();
+        assert_eq!((day.solve)(Part::One, "(()(()(").unwrap(), "3");
+    }
+}
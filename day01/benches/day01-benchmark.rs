@@ -1,49 +1,50 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use day01::{parse_input, solve_part1, solve_part2};
+use criterion::{black_box, Criterion, Throughput};
+use day01::{solve_part1, solve_part2, vectorized};
 
-fn benchmark_part1(c: &mut Criterion) {
-    let input_path = format!("../inputs/{}.txt", env!("CARGO_PKG_NAME"));
-    let parsed = parse_input(input_path).unwrap();
-    c.bench_function("day 01 - part 1", |b| {
-        b.iter(|| solve_part1(black_box(&parsed)))
-    });
-}
+const LARGE: usize = 100 * 1024 * 1024;
 
-fn benchmark_part2(c: &mut Criterion) {
-    let input_path = format!("../inputs/{}.txt", env!("CARGO_PKG_NAME"));
-    let parsed = parse_input(input_path).unwrap();
-    c.bench_function("day 01 - part 2", |b| {
-        b.iter(|| solve_part2(black_box(&parsed)))
-    });
+// A random walk staying above the ground floor for 90% of the input, then
+// heading down, so part 2 has to scan almost everything
+fn generate(len: usize) -> Vec<u8> {
+    let mut state: u32 = 0x2015_0001;
+    let mut floor = 0;
+    (0..len)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let up = i <= len * 9 / 10 && (floor == 0 || state.is_multiple_of(2));
+            floor += if up { 1 } else { -1 };
+            if up {
+                b'('
+            } else {
+                b')'
+            }
+        })
+        .collect()
 }
 
-fn benchmark_full_solution(c: &mut Criterion) {
-    let mut group = c.benchmark_group("day 01 - full solution");
-    //let input = include_str!("../../inputs/day01.txt");
-    let input_path = format!("../inputs/{}.txt", env!("CARGO_PKG_NAME"));
-    let input_path = input_path.as_str();
+// The scalar solvers on parsed directions against the vectorized ones on raw bytes
+fn benchmark_large(c: &mut Criterion) {
+    let bytes = generate(LARGE);
+    let directions = day01::parse(std::str::from_utf8(&bytes).unwrap()).unwrap();
 
-    group.bench_function("parse + part1", |b| {
-        b.iter(|| {
-            let parsed = parse_input(black_box(input_path.to_string())).unwrap();
-            solve_part1(&parsed)
-        })
+    let mut group = c.benchmark_group("day 01 - 100MB");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(LARGE as u64));
+    group.bench_function("scalar part1", |b| {
+        b.iter(|| solve_part1(black_box(&directions)))
     });
-
-    group.bench_function("parse + part2", |b| {
-        b.iter(|| {
-            let parsed = parse_input(black_box(input_path.to_string())).unwrap();
-            solve_part2(&parsed)
-        })
+    group.bench_function("vectorized part1", |b| {
+        b.iter(|| vectorized::final_floor(black_box(&bytes)))
+    });
+    group.bench_function("scalar part2", |b| {
+        b.iter(|| solve_part2(black_box(&directions)))
+    });
+    group.bench_function("vectorized part2", |b| {
+        b.iter(|| vectorized::first_basement_step(black_box(&bytes)))
     });
-
     group.finish();
 }
 
-criterion_group!(
-    benches,
-    benchmark_part1,
-    benchmark_part2,
-    benchmark_full_solution
-);
-criterion_main!(benches);
+aoc_common::aoc_bench!(day01::Day01, benchmark_large);
@@ -0,0 +1,40 @@
+// Solve day 1 from a stream too large to hold in memory, e.g.
+// `generate | cargo run --release -p day01 --bin day01-stream`
+use anyhow::{anyhow, Context, Result};
+use day01::{FloorTracker, LexMode};
+use std::fs::File;
+use std::io;
+
+fn main() -> Result<()> {
+    let mut mode = LexMode::Strict;
+    let mut path = None;
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "-w" | "--ignore-whitespace" => mode = LexMode::IgnoreWhitespace,
+            "-h" | "--help" => {
+                println!("Usage: day01-stream [--ignore-whitespace] [<path>|-]");
+                println!("  reads the directions from <path>, or stdin when missing or -");
+                return Ok(());
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(anyhow!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    let mut tracker = FloorTracker::new(mode);
+    let on_basement = |step| println!("Santa enters the basement at step {}", step);
+    match path.as_deref() {
+        None | Some("-") => tracker.read_from(io::stdin().lock(), on_basement)?,
+        Some(path) => {
+            let file = File::open(path).with_context(|| format!("Could not open {}", path))?;
+            tracker.read_from(file, on_basement)?
+        }
+    }
+
+    println!("Part 1: {}", tracker.floor());
+    match tracker.basement_step() {
+        Some(step) => println!("Part 2: {}", step),
+        None => println!("Part 2: never"),
+    }
+    Ok(())
+}
@@ -1,34 +1,325 @@
-use anyhow::{Context, Result};
-use itertools::{
-    FoldWhile::{Continue, Done},
-    Itertools,
-};
-use std::{fs::read_to_string, i32};
-
-pub fn parse_input(input_path: String) -> Result<Vec<i32>, anyhow::Error> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
-    let directions: Vec<i32> = input_contents
-        .chars()
-        .map(|c| if c == '(' { 1 } else { -1 })
-        .collect();
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod vectorized;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use anyhow::Result;
+use aoc_common::{Answer, AnswerKind, Explain, Metadata, Part, Solution, Tag};
+use core::fmt;
+
+// Whether whitespace between the parentheses is an error or skipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexMode {
+    Strict,
+    IgnoreWhitespace,
+}
+
+// The first character that is not a direction, with its byte offset in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidToken {
+    pub offset: usize,
+    pub found: char,
+}
+
+impl fmt::Display for InvalidToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid instruction {:?} at byte {}, expected '(' or ')'",
+            self.found, self.offset
+        )
+    }
+}
+
+impl core::error::Error for InvalidToken {}
+
+// `(` goes up a floor and `)` down, anything else is rejected
+pub fn lex(input: &str, mode: LexMode) -> Result<Vec<i32>, InvalidToken> {
+    let mut directions = Vec::with_capacity(input.len());
+    for (offset, found) in input.char_indices() {
+        match found {
+            '(' => directions.push(1),
+            ')' => directions.push(-1),
+            c if c.is_whitespace() && mode == LexMode::IgnoreWhitespace => {}
+            _ => return Err(InvalidToken { offset, found }),
+        }
+    }
     Ok(directions)
 }
 
-pub fn solve_part1(directions: &Vec<i32>) -> i32 {
+// Strict apart from the line ending the input file
+pub fn parse(input_contents: &str) -> Result<Vec<i32>, anyhow::Error> {
+    Ok(lex(input_contents.trim_end(), LexMode::Strict)?)
+}
+
+pub fn solve_part1(directions: &[i32]) -> i32 {
     directions.iter().sum()
 }
 
-pub fn solve_part2(directions: &Vec<i32>) -> usize {
-    let (_, last_index) = directions
+// 1-based step on which Santa first stands on `target_floor`, 0 for the ground
+// floor he starts on, `None` if the directions never take him there
+pub fn first_step_reaching(directions: &[i32], target_floor: i32) -> Option<usize> {
+    if target_floor == 0 {
+        return Some(0);
+    }
+    directions
         .iter()
-        .enumerate()
-        .fold_while((0, 0), |(acc, _), (idx, &x)| {
-            if acc + x == -1 {
-                Done((acc, idx))
-            } else {
-                Continue((acc + x, idx))
-            }
+        .scan(0, |floor, &d| {
+            *floor += d;
+            Some(*floor)
         })
-        .into_inner();
-    last_index + 1 // It's a 1-based floor counting
+        .position(|floor| floor == target_floor)
+        .map(|idx| idx + 1)
+}
+
+pub fn solve_part2(directions: &[i32]) -> Option<usize> {
+    first_step_reaching(directions, -1)
+}
+
+// Lowest or highest floor reached, and the first step reaching it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extreme {
+    pub floor: i32,
+    pub step: usize,
+}
+
+// Everything about Santa's trip through the building. Steps are 1-based as
+// in the puzzle, step 0 being the start on floor 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FloorTrace {
+    pub steps: usize,
+    pub final_floor: i32,
+    pub lowest: Extreme,
+    pub highest: Extreme,
+    // Steps going from floor 0 to -1, and from -1 back to 0
+    pub basement_entries: Vec<usize>,
+    pub basement_exits: Vec<usize>,
+    // How many of the positions at steps 0..=steps were on each floor
+    pub time_on_floor: BTreeMap<i32, usize>,
+    first_steps: BTreeMap<i32, usize>,
+}
+
+impl FloorTrace {
+    pub fn new(directions: &[i32]) -> Self {
+        let start = Extreme { floor: 0, step: 0 };
+        let mut trace = FloorTrace {
+            steps: directions.len(),
+            final_floor: 0,
+            lowest: start,
+            highest: start,
+            basement_entries: Vec::new(),
+            basement_exits: Vec::new(),
+            time_on_floor: BTreeMap::from([(0, 1)]),
+            first_steps: BTreeMap::from([(0, 0)]),
+        };
+
+        let mut floor = 0;
+        for (idx, &d) in directions.iter().enumerate() {
+            let step = idx + 1;
+            let previous = floor;
+            floor += d;
+
+            match (previous, floor) {
+                (0, -1) => trace.basement_entries.push(step),
+                (-1, 0) => trace.basement_exits.push(step),
+                _ => {}
+            }
+            if floor < trace.lowest.floor {
+                trace.lowest = Extreme { floor, step };
+            }
+            if floor > trace.highest.floor {
+                trace.highest = Extreme { floor, step };
+            }
+            *trace.time_on_floor.entry(floor).or_insert(0) += 1;
+            trace.first_steps.entry(floor).or_insert(step);
+        }
+        trace.final_floor = floor;
+        trace
+    }
+
+    // Floors change one at a time, so every floor between the extremes is reached
+    pub fn first_step_reaching(&self, floor: i32) -> Option<usize> {
+        self.first_steps.get(&floor).copied()
+    }
+}
+
+// Walks the directions as they arrive, in chunks of bytes, keeping only the
+// current floor and position: inputs of any size run in constant memory.
+// Whitespace is ASCII whitespace here, and in strict mode it is only allowed
+// at the end of the input, like `parse` does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FloorTracker {
+    mode: LexMode,
+    floor: i64,
+    steps: usize,
+    offset: usize,
+    basement_step: Option<usize>,
+    // Strict mode: whitespace seen so far, an error if more directions follow
+    trailing_whitespace: Option<InvalidToken>,
+}
+
+impl FloorTracker {
+    pub fn new(mode: LexMode) -> Self {
+        FloorTracker {
+            mode,
+            floor: 0,
+            steps: 0,
+            offset: 0,
+            basement_step: None,
+            trailing_whitespace: None,
+        }
+    }
+
+    // Consume the next chunk, calling `on_basement` as soon as Santa first
+    // enters the basement, even if the chunk goes on to an invalid byte
+    pub fn feed(
+        &mut self,
+        chunk: &[u8],
+        mut on_basement: impl FnMut(usize),
+    ) -> Result<(), InvalidToken> {
+        for &byte in chunk {
+            let offset = self.offset;
+            self.offset += 1;
+            let direction = match byte {
+                b'(' => 1,
+                b')' => -1,
+                b if b.is_ascii_whitespace() => {
+                    if self.mode == LexMode::Strict && self.trailing_whitespace.is_none() {
+                        self.trailing_whitespace = Some(InvalidToken {
+                            offset,
+                            found: b as char,
+                        });
+                    }
+                    continue;
+                }
+                b => {
+                    // Bytes are not decoded, anything outside ASCII is reported as U+FFFD
+                    let found = if b.is_ascii() {
+                        b as char
+                    } else {
+                        char::REPLACEMENT_CHARACTER
+                    };
+                    return Err(InvalidToken { offset, found });
+                }
+            };
+            if let Some(whitespace) = self.trailing_whitespace {
+                return Err(whitespace);
+            }
+
+            self.steps += 1;
+            self.floor += direction;
+            if self.floor == -1 && self.basement_step.is_none() {
+                self.basement_step = Some(self.steps);
+                on_basement(self.steps);
+            }
+        }
+        Ok(())
+    }
+
+    // Read everything from `reader` (a file, stdin, a socket...), calling
+    // `on_basement` as soon as Santa first enters the basement
+    #[cfg(feature = "std")]
+    pub fn read_from<R: std::io::Read>(
+        &mut self,
+        mut reader: R,
+        mut on_basement: impl FnMut(usize),
+    ) -> Result<()> {
+        let mut buffer = [0; 64 * 1024];
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(read) => read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            };
+            self.feed(&buffer[..read], &mut on_basement)?;
+        }
+    }
+
+    pub fn floor(&self) -> i64 {
+        self.floor
+    }
+
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    pub fn basement_step(&self) -> Option<usize> {
+        self.basement_step
+    }
+}
+
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 1,
+    title: "Not Quite Lisp",
+    input: "A single line of `(` and `)`",
+    part1: Answer {
+        label: "final floor",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "first step into the basement",
+        kind: AnswerKind::Text,
+    },
+    tags: &[Tag::Simulation, Tag::Strings],
+};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<i32>;
+    type Part1 = i32;
+    type Part2 = String;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> Result<Vec<i32>> {
+        parse(input)
+    }
+
+    fn part1(directions: &Vec<i32>) -> i32 {
+        solve_part1(directions)
+    }
+
+    fn part2(directions: &Vec<i32>) -> String {
+        match solve_part2(directions) {
+            Some(step) => step.to_string(),
+            None => "never".to_string(),
+        }
+    }
+}
+
+impl Explain for Day01 {
+    fn explain(directions: &Vec<i32>, part: Part) -> String {
+        match part {
+            Part::One => {
+                let ups = directions.iter().filter(|&&d| d == 1).count();
+                let downs = directions.len() - ups;
+                format!(
+                    "{} steps up and {} steps down: Santa ends on floor {}",
+                    ups,
+                    downs,
+                    solve_part1(directions)
+                )
+            }
+            Part::Two => match first_step_reaching(directions, -1) {
+                Some(step) => format!(
+                    "Santa enters the basement at step {}, going down from floor 0",
+                    step
+                ),
+                None => format!(
+                    "Santa never enters the basement in {} steps, ending on floor {}",
+                    directions.len(),
+                    solve_part1(directions)
+                ),
+            },
+        }
+    }
 }
@@ -1,33 +1,200 @@
-use anyhow::Result;
-use day01::{parse_input, solve_part1, solve_part2};
-
-fn main() -> Result<()> {
-    // Read the input file that matches the Cargo Package name
-    let input_path = format!("../inputs/{}.txt", env!("CARGO_PKG_NAME"));
-    let directions = parse_input(input_path)?;
-
-    println!("Part 1: {}", solve_part1(&directions));
-    println!("Part 2: {}", solve_part2(&directions));
-    Ok(())
-}
+aoc_common::aoc_main!(day01::Day01);
 
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use day01::{
+        first_step_reaching, lex, parse, solve_part1, solve_part2, Extreme, FloorTrace,
+        FloorTracker, InvalidToken, LexMode,
+    };
+    use std::collections::BTreeMap;
 
     #[test]
     fn test_solve_part1() {
-        assert_eq!(solve_part1(&vec![1, -1]), 0);
-        assert_eq!(solve_part1(&vec![1, 1, 1]), 3);
-        assert_eq!(solve_part1(&vec![-1, -1, -1]), -3);
-        assert_eq!(solve_part1(&vec![1, 1, -1, -1]), 0);
+        assert_eq!(solve_part1(&[1, -1]), 0);
+        assert_eq!(solve_part1(&[1, 1, 1]), 3);
+        assert_eq!(solve_part1(&[-1, -1, -1]), -3);
+        assert_eq!(solve_part1(&[1, 1, -1, -1]), 0);
     }
 
     #[test]
     fn test_solve_part2() {
-        assert_eq!(solve_part2(&vec![-1]), 1);
-        assert_eq!(solve_part2(&vec![1, 1, -1, -1, -1]), 5);
-        assert_eq!(solve_part2(&vec![-1, -1]), 1);
-        assert_eq!(solve_part2(&vec![-1, 1]), 1);
+        assert_eq!(solve_part2(&[-1]), Some(1));
+        assert_eq!(solve_part2(&[1, 1, -1, -1, -1]), Some(5));
+        assert_eq!(solve_part2(&[-1, -1]), Some(1));
+        assert_eq!(solve_part2(&[-1, 1]), Some(1));
+        assert_eq!(solve_part2(&[1, -1, 1]), None);
+        assert_eq!(solve_part2(&[]), None);
+    }
+
+    #[test]
+    fn test_first_step_reaching() {
+        let directions = [1, -1, -1, -1, 1, 1, 1, 1, -1];
+        assert_eq!(first_step_reaching(&directions, 0), Some(0));
+        assert_eq!(first_step_reaching(&directions, 1), Some(1));
+        assert_eq!(first_step_reaching(&directions, -2), Some(4));
+        assert_eq!(first_step_reaching(&directions, 2), Some(8));
+        assert_eq!(first_step_reaching(&directions, 3), None);
+        assert_eq!(first_step_reaching(&directions, -3), None);
+
+        // Agrees with the full trace
+        let trace = FloorTrace::new(&directions);
+        for floor in -4..=4 {
+            assert_eq!(
+                first_step_reaching(&directions, floor),
+                trace.first_step_reaching(floor)
+            );
+        }
+    }
+
+    #[test]
+    fn test_part2_never() {
+        use aoc_common::Solution;
+        use day01::Day01;
+
+        assert_eq!(Day01::part2(&vec![1, -1, -1]), "3");
+        assert_eq!(Day01::part2(&vec![1, -1, 1]), "never");
+        assert_eq!(Day01::METADATA.part2.kind, aoc_common::AnswerKind::Text);
+    }
+
+    #[test]
+    fn test_explain() {
+        use aoc_common::{Explain, Part};
+        use day01::Day01;
+
+        assert_eq!(
+            Day01::explain(&vec![1, 1, -1], Part::One),
+            "2 steps up and 1 steps down: Santa ends on floor 1"
+        );
+        assert_eq!(
+            Day01::explain(&vec![1, -1, -1, 1], Part::Two),
+            "Santa enters the basement at step 3, going down from floor 0"
+        );
+        assert_eq!(
+            Day01::explain(&vec![1, 1], Part::Two),
+            "Santa never enters the basement in 2 steps, ending on floor 2"
+        );
+    }
+
+    #[test]
+    fn test_lex() {
+        assert_eq!(lex("(()", LexMode::Strict), Ok(vec![1, 1, -1]));
+        assert_eq!(
+            lex("((\n)", LexMode::Strict),
+            Err(InvalidToken {
+                offset: 2,
+                found: '\n'
+            })
+        );
+        assert_eq!(lex("( (\n)", LexMode::IgnoreWhitespace), Ok(vec![1, 1, -1]));
+
+        // Offsets are in bytes, not characters
+        assert_eq!(
+            lex("(é)x", LexMode::IgnoreWhitespace),
+            Err(InvalidToken {
+                offset: 1,
+                found: 'é'
+            })
+        );
+        assert_eq!(
+            lex("()\u{0}", LexMode::Strict).unwrap_err().to_string(),
+            "Invalid instruction '\\0' at byte 2, expected '(' or ')'"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("(()\n").unwrap(), vec![1, 1, -1]);
+        assert!(parse("( )").is_err());
+    }
+
+    #[test]
+    fn test_floor_trace() {
+        // ( ) ) ) ( ( ( ( )
+        let trace = FloorTrace::new(&[1, -1, -1, -1, 1, 1, 1, 1, -1]);
+        assert_eq!(trace.steps, 9);
+        assert_eq!(trace.final_floor, 1);
+        assert_eq!(trace.lowest, Extreme { floor: -2, step: 4 });
+        assert_eq!(trace.highest, Extreme { floor: 2, step: 8 });
+        assert_eq!(trace.basement_entries, vec![3]);
+        assert_eq!(trace.basement_exits, vec![6]);
+        assert_eq!(
+            trace.time_on_floor,
+            BTreeMap::from([(-2, 1), (-1, 2), (0, 3), (1, 3), (2, 1)])
+        );
+        assert_eq!(trace.first_step_reaching(0), Some(0));
+        assert_eq!(trace.first_step_reaching(-1), Some(3));
+        assert_eq!(trace.first_step_reaching(2), Some(8));
+        assert_eq!(trace.first_step_reaching(3), None);
+        assert_eq!(trace.first_step_reaching(-3), None);
+    }
+
+    #[test]
+    fn test_floor_tracker() {
+        let input = "(()))()(\n";
+        for chunk_size in 1..=input.len() {
+            let mut tracker = FloorTracker::new(LexMode::Strict);
+            let mut events = vec![];
+            for (idx, chunk) in input.as_bytes().chunks(chunk_size).enumerate() {
+                tracker
+                    .feed(chunk, |step| events.push((idx, step)))
+                    .unwrap();
+            }
+            assert_eq!(tracker.floor(), 0);
+            assert_eq!(tracker.steps(), 8);
+            assert_eq!(tracker.basement_step(), Some(5));
+            // Reported once, with the chunk holding step 5
+            assert_eq!(events, vec![(4 / chunk_size, 5)]);
+        }
+
+        let mut events = vec![];
+        let mut tracker = FloorTracker::new(LexMode::Strict);
+        assert_eq!(tracker.feed(b"(( ", |step| events.push(step)), Ok(()));
+        assert_eq!(
+            tracker.feed(b")", |step| events.push(step)),
+            Err(InvalidToken {
+                offset: 2,
+                found: ' '
+            })
+        );
+
+        let mut tracker = FloorTracker::new(LexMode::IgnoreWhitespace);
+        assert_eq!(tracker.feed(b"( )\n)", |step| events.push(step)), Ok(()));
+        assert_eq!(
+            tracker.feed("(é".as_bytes(), |step| events.push(step)),
+            Err(InvalidToken {
+                offset: 6,
+                found: char::REPLACEMENT_CHARACTER
+            })
+        );
+        assert_eq!(events, vec![3]);
+
+        // The basement is reported before the invalid byte later in the chunk
+        let mut events = vec![];
+        let mut tracker = FloorTracker::new(LexMode::Strict);
+        assert!(tracker.feed(b"())x", |step| events.push(step)).is_err());
+        assert_eq!(events, vec![3]);
+    }
+
+    #[test]
+    fn test_floor_tracker_read_from() {
+        let input = "(".repeat(100_000) + &")".repeat(100_001) + "(";
+        let mut tracker = FloorTracker::new(LexMode::Strict);
+        let mut events = vec![];
+        tracker
+            .read_from(input.as_bytes(), |step| events.push(step))
+            .unwrap();
+        assert_eq!(events, vec![200_001]);
+        assert_eq!(tracker.floor(), 0);
+
+        let directions = parse(&input).unwrap();
+        assert_eq!(tracker.floor(), solve_part1(&directions) as i64);
+        assert_eq!(tracker.basement_step(), solve_part2(&directions));
+
+        let mut events = vec![];
+        let mut tracker = FloorTracker::new(LexMode::Strict);
+        assert!(tracker
+            .read_from("())x".as_bytes(), |step| events.push(step))
+            .is_err());
+        assert_eq!(events, vec![3]);
     }
 }
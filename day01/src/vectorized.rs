@@ -0,0 +1,167 @@
+//! Day 1 on raw bytes for very large inputs.
+//!
+//! `(` goes up and `)` down, any other byte is skipped but still counts as a
+//! step, so inputs should be checked with [`lex`](crate::lex) first. Inner
+//! loops run over fixed blocks of bytes so they compile to SIMD compares and
+//! sums, and with the `parallel` feature chunks are summarized on all cores.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Bytes per chunk summarized independently for part 2
+const CHUNK: usize = 1 << 20;
+
+// Bytes per block: their net change fits the i8 lanes the compiler
+// vectorizes the sum into
+const BLOCK: usize = 127;
+
+fn step(b: u8) -> i64 {
+    (b == b'(') as i64 - (b == b')') as i64
+}
+
+fn delta(bytes: &[u8]) -> i64 {
+    bytes
+        .chunks(BLOCK)
+        .map(|block| {
+            block
+                .iter()
+                .map(|&b| (b == b'(') as i8 - (b == b')') as i8)
+                .sum::<i8>() as i64
+        })
+        .sum()
+}
+
+// Final floor: the `(` count less the `)` count
+#[cfg(not(feature = "parallel"))]
+pub fn final_floor(bytes: &[u8]) -> i64 {
+    delta(bytes)
+}
+
+#[cfg(feature = "parallel")]
+pub fn final_floor(bytes: &[u8]) -> i64 {
+    bytes.par_chunks(CHUNK).map(delta).sum()
+}
+
+// Net change over a chunk, and the lowest floor relative to its start
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Summary {
+    delta: i64,
+    lowest: i64,
+}
+
+// The floor drops by at most one per byte, so only blocks starting within
+// their length of the lowest floor so far need a step by step scan
+fn summarize(chunk: &[u8]) -> Summary {
+    let mut floor = 0;
+    let mut lowest = 0;
+    for block in chunk.chunks(BLOCK) {
+        if floor - block.len() as i64 >= lowest {
+            floor += delta(block);
+            continue;
+        }
+        for &b in block {
+            floor += step(b);
+            lowest = lowest.min(floor);
+        }
+    }
+    Summary {
+        delta: floor,
+        lowest,
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn summaries(bytes: &[u8]) -> impl Iterator<Item = Summary> + '_ {
+    bytes.chunks(CHUNK).map(summarize)
+}
+
+#[cfg(feature = "parallel")]
+fn summaries(bytes: &[u8]) -> impl Iterator<Item = Summary> {
+    let summaries: alloc::vec::Vec<Summary> = bytes.par_chunks(CHUNK).map(summarize).collect();
+    summaries.into_iter()
+}
+
+// 1-based step first reaching floor -1. Chunk prefix sums locate the first
+// chunk going below its starting floor far enough, which is then scanned
+pub fn first_basement_step(bytes: &[u8]) -> Option<usize> {
+    let mut floor = 0;
+    for (idx, summary) in summaries(bytes).enumerate() {
+        // Floors change one at a time, so going at or below -1 means hitting -1
+        if floor + summary.lowest > -1 {
+            floor += summary.delta;
+            continue;
+        }
+        let start = idx * CHUNK;
+        let chunk = &bytes[start..(start + CHUNK).min(bytes.len())];
+        for (block_idx, block) in chunk.chunks(BLOCK).enumerate() {
+            if floor - block.len() as i64 > -1 {
+                floor += delta(block);
+                continue;
+            }
+            for (offset, &b) in block.iter().enumerate() {
+                floor += step(b);
+                if floor == -1 {
+                    return Some(start + block_idx * BLOCK + offset + 1);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{first_step_reaching, parse, solve_part1};
+    use alloc::string::String;
+
+    // A walk that wanders above the ground floor before heading down
+    fn generate(len: usize) -> String {
+        let mut state: u32 = 0x2015_0001;
+        let mut floor = 0;
+        (0..len)
+            .map(|i| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                let up = if i > len * 9 / 10 {
+                    false
+                } else {
+                    floor == 0 || state.is_multiple_of(2)
+                };
+                floor += if up { 1 } else { -1 };
+                if up {
+                    '('
+                } else {
+                    ')'
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_scalar() {
+        for input in ["", "(", ")", "()())", "(()(()(", "))(((((", "(\n"] {
+            let directions = parse(input).unwrap();
+            assert_eq!(
+                final_floor(input.as_bytes()),
+                solve_part1(&directions) as i64
+            );
+            assert_eq!(
+                first_basement_step(input.trim_end().as_bytes()),
+                first_step_reaching(&directions, -1)
+            );
+        }
+
+        // Across several chunks, the basement well past the first one
+        let input = generate(3 * CHUNK + 12_345);
+        let directions = parse(&input).unwrap();
+        let step = first_basement_step(input.as_bytes());
+        assert!(step.unwrap() > CHUNK);
+        assert_eq!(step, first_step_reaching(&directions, -1));
+        assert_eq!(
+            final_floor(input.as_bytes()),
+            solve_part1(&directions) as i64
+        );
+    }
+}
@@ -0,0 +1,71 @@
+// How many rolls of wrapping paper to buy for the order, and how much is wasted
+use anyhow::{anyhow, Result};
+use day02::rolls::{exact_pack, pieces, shelf_pack, Roll, MAX_EXACT_PIECES};
+use day02::Day02;
+
+// "3x100" -> a roll 3 feet wide and 100 feet long
+fn parse_roll(roll: &str) -> Result<Roll> {
+    let (width, length) = roll
+        .split_once('x')
+        .ok_or_else(|| anyhow!("Expected a roll as WIDTHxLENGTH, found {:?}", roll))?;
+    let dimension = |value: &str| match value.parse::<u32>() {
+        Ok(0) => Err(anyhow!("Roll dimensions must be positive")),
+        Ok(value) => Ok(value),
+        Err(e) => Err(anyhow!("Invalid roll dimension {:?}: {}", value, e)),
+    };
+    Ok(Roll {
+        width: dimension(width)?,
+        length: dimension(length)?,
+    })
+}
+
+fn main() -> Result<()> {
+    let mut roll = None;
+    let mut exact = false;
+    let mut path = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-i" | "--input" => {
+                path = Some(args.next().ok_or_else(|| anyhow!("{} needs a path", arg))?);
+            }
+            "--roll" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| anyhow!("{} needs WIDTHxLENGTH", arg))?;
+                roll = Some(parse_roll(&value)?);
+            }
+            "--exact" => exact = true,
+            "-h" | "--help" => {
+                println!("Usage: day02-rolls --roll <WIDTHxLENGTH> [--exact] [--input <path>]");
+                println!("  --roll <WxL>  paper roll size in feet");
+                println!(
+                    "  --exact       fewest rolls possible, for orders of up to {} pieces",
+                    MAX_EXACT_PIECES
+                );
+                return Ok(());
+            }
+            _ => return Err(anyhow!("Unexpected argument: {}", arg)),
+        }
+    }
+    let roll = roll.ok_or_else(|| anyhow!("Missing --roll <WIDTHxLENGTH>"))?;
+
+    let input = aoc_common::read_input_arg::<Day02>(path.as_deref(), "day02")?;
+    let cuts = pieces(&day02::parse(&input)?);
+    let layout = if exact {
+        exact_pack(&cuts, roll)?
+    } else {
+        shelf_pack(&cuts, roll)?
+    };
+
+    println!(
+        "{} rolls of {}x{} feet for {} pieces, {:.1}% waste",
+        layout.rolls,
+        roll.width,
+        roll.length,
+        cuts.len(),
+        layout.waste_percent()
+    );
+    Ok(())
+}
@@ -0,0 +1,73 @@
+// Load the order into sleighs, optionally listing where each present goes
+// and exporting the layout as an OBJ mesh
+use anyhow::{anyhow, Context, Result};
+use day02::sleigh::{load, Sleigh};
+use day02::Day02;
+use std::fs::write;
+
+fn main() -> Result<()> {
+    let mut sleigh: Option<Sleigh> = None;
+    let mut placements = false;
+    let mut obj = None;
+    let mut path = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-i" | "--input" => {
+                path = Some(args.next().ok_or_else(|| anyhow!("{} needs a path", arg))?);
+            }
+            "--sleigh" => {
+                let value = args.next().ok_or_else(|| anyhow!("{} needs LxWxH", arg))?;
+                sleigh = Some(value.parse().context("Invalid sleigh")?);
+            }
+            "--placements" => placements = true,
+            "--obj" => obj = Some(args.next().ok_or_else(|| anyhow!("{} needs a path", arg))?),
+            "-h" | "--help" => {
+                println!(
+                    "Usage: day02-sleigh --sleigh <LxWxH> [--placements] [--obj <path>] [--input <path>]"
+                );
+                println!("  --sleigh <LxWxH>  cargo space of a sleigh in feet");
+                println!("  --placements      list the sleigh and position of every present");
+                println!("  --obj <path>      write the layout as a Wavefront OBJ mesh");
+                return Ok(());
+            }
+            _ => return Err(anyhow!("Unexpected argument: {}", arg)),
+        }
+    }
+    let sleigh = sleigh.ok_or_else(|| anyhow!("Missing --sleigh <LxWxH>"))?;
+
+    let input = aoc_common::read_input_arg::<Day02>(path.as_deref(), "day02")?;
+    let presents = day02::parse(&input)?;
+    let loading = load(&presents, sleigh)?;
+
+    println!(
+        "{} presents in {} sleighs of {}x{}x{} feet",
+        presents.len(),
+        loading.sleighs,
+        sleigh.length,
+        sleigh.width,
+        sleigh.height
+    );
+    if placements {
+        for placement in &loading.placements {
+            let [x, y, z] = placement.position;
+            let [l, w, h] = placement.size;
+            println!(
+                "  present {} as {}x{}x{}: sleigh {} at ({}, {}, {})",
+                placement.present + 1,
+                l,
+                w,
+                h,
+                placement.sleigh + 1,
+                x,
+                y,
+                z
+            );
+        }
+    }
+    if let Some(obj) = obj {
+        write(&obj, loading.to_obj()).with_context(|| format!("Could not write {}", obj))?;
+    }
+    Ok(())
+}
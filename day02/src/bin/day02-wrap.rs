@@ -0,0 +1,54 @@
+// Compare the paper and ribbon an order takes under different wrapping policies
+use anyhow::{anyhow, Result};
+use day02::policy::{find_policy, materials, WrappingPolicy, POLICIES};
+use day02::Day02;
+
+fn main() -> Result<()> {
+    let names: Vec<&str> = POLICIES.iter().map(|policy| policy.name()).collect();
+    let mut policies: Vec<&dyn WrappingPolicy> = POLICIES.to_vec();
+    let mut path = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-i" | "--input" => {
+                path = Some(args.next().ok_or_else(|| anyhow!("{} needs a path", arg))?);
+            }
+            "--policy" => {
+                let name = args.next().ok_or_else(|| anyhow!("{} needs a name", arg))?;
+                let policy = find_policy(&name).ok_or_else(|| {
+                    anyhow!(
+                        "Unknown policy {}, expected one of {}",
+                        name,
+                        names.join(", ")
+                    )
+                })?;
+                policies = vec![policy];
+            }
+            "-h" | "--help" => {
+                println!("Usage: day02-wrap [--input <path>] [--policy <name>]");
+                println!(
+                    "  --policy <name>  one of {} (default: all)",
+                    names.join(", ")
+                );
+                return Ok(());
+            }
+            _ => return Err(anyhow!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    let input = aoc_common::read_input_arg::<Day02>(path.as_deref(), "day02")?;
+    let presents = day02::parse(&input)?;
+
+    let width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+    for policy in policies {
+        let (paper, ribbon) = materials(&presents, policy)?;
+        println!(
+            "{:width$}  {} square feet of paper, {} feet of ribbon",
+            policy.name(),
+            paper,
+            ribbon
+        );
+    }
+    Ok(())
+}
@@ -1,65 +1,288 @@
-use anyhow::{Context, Result};
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use itertools::Itertools;
-use std::{fs::read_to_string, i32};
+extern crate alloc;
 
-pub fn parse_input(input_path: String) -> Result<Vec<Vec<i32>>, anyhow::Error> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
+pub mod policy;
+pub mod rolls;
+pub mod sleigh;
 
-    let dimensions: Vec<Vec<i32>> = input_contents
+use alloc::format;
+use alloc::vec::Vec;
+use anyhow::{anyhow, Context, Result};
+use aoc_common::{Answer, AnswerKind, Metadata, Solution, Tag};
+use core::str::FromStr;
+
+// A present's length, width and height in feet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Present {
+    pub l: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+impl FromStr for Present {
+    type Err = anyhow::Error;
+
+    // `LxWxH`, each dimension a positive integer
+    fn from_str(s: &str) -> Result<Self> {
+        let dimensions = s
+            .trim()
+            .split('x')
+            .map(|dimension| {
+                dimension
+                    .parse::<u32>()
+                    .map_err(|e| anyhow!("Invalid dimension {:?}: {}", dimension, e))
+            })
+            .collect::<Result<Vec<u32>>>()?;
+        match dimensions[..] {
+            [l, w, h] => Present::new(l, w, h),
+            _ => Err(anyhow!(
+                "Expected LxWxH, found {} dimensions in {:?}",
+                dimensions.len(),
+                s
+            )),
+        }
+    }
+}
+
+impl Present {
+    pub fn new(l: u32, w: u32, h: u32) -> Result<Present> {
+        if [l, w, h].contains(&0) {
+            return Err(anyhow!("Dimensions must be positive, found 0"));
+        }
+        Ok(Present { l, w, h })
+    }
+}
+
+// Quantities are widened to u128: with u32 dimensions no single present can
+// overflow them, but ribbon reaches about 2^96 feet a present, so a total of
+// a little over 2^32 of the largest presents can
+impl Present {
+    fn dimensions(&self) -> [u128; 3] {
+        [self.l as u128, self.w as u128, self.h as u128]
+    }
+
+    // Areas of the three distinct sides
+    pub fn sides(&self) -> [u128; 3] {
+        let [l, w, h] = self.dimensions();
+        [l * w, w * h, h * l]
+    }
+
+    pub fn surface_area(&self) -> u128 {
+        self.sides().iter().map(|side| 2 * side).sum()
+    }
+
+    // Area of the smallest side, the slack added to the paper
+    pub fn smallest_side(&self) -> u128 {
+        self.sides().into_iter().min().unwrap_or(0)
+    }
+
+    pub fn volume(&self) -> u128 {
+        let [l, w, h] = self.dimensions();
+        l * w * h
+    }
+
+    // Wrapping paper: the surface plus the smallest side as slack
+    pub fn paper(&self) -> u128 {
+        self.surface_area() + self.smallest_side()
+    }
+
+    // Ribbon: the smallest perimeter around the present plus a bow as long as its volume
+    pub fn ribbon(&self) -> u128 {
+        let [l, w, h] = self.dimensions();
+        let smallest_perimeter = 2 * [l + w, w + h, h + l].into_iter().min().unwrap_or(0);
+        smallest_perimeter + self.volume()
+    }
+}
+
+pub fn parse(input_contents: &str) -> Result<Vec<Present>, anyhow::Error> {
+    input_contents
         .lines()
-        .map(|line| -> Vec<i32> { line.split('x').map(|s| s.parse().unwrap()).collect() })
-        .collect();
+        .enumerate()
+        .map(|(idx, line)| line.parse().with_context(|| format!("Line {}", idx + 1)))
+        .collect()
+}
 
-    Ok(dimensions)
+fn checked_total(quantities: impl IntoIterator<Item = u128>, what: &str) -> Result<u128> {
+    quantities
+        .into_iter()
+        .try_fold(0u128, |total, quantity| total.checked_add(quantity))
+        .ok_or_else(|| anyhow!("Total {} overflows u128", what))
 }
 
-pub fn solve_part1(dimensions_list: &Vec<Vec<i32>>) -> i32 {
-    let areas: Vec<i32> = dimensions_list
-        .iter()
-        .map(|d| {
-            //println!("here is D:{:?}", d);
-            let sides: Vec<i32> = d.iter().tuple_combinations().map(|(a, b)| a * b).collect();
-            let min_side: i32 = sides.iter().min().unwrap().clone();
-            //println!("{:?}", sides);
-            let area: i32 = sides.iter().map(|s| s * 2).sum();
-            area + min_side //slack
-        })
-        .collect::<Vec<i32>>();
-    areas.iter().sum()
+pub fn solve_part1(presents: &[Present]) -> Result<u128> {
+    checked_total(presents.iter().map(Present::paper), "paper")
 }
 
-pub fn solve_part2(dimensions_list: &Vec<Vec<i32>>) -> i32 {
-    let lengths: Vec<i32> = dimensions_list
-        .iter()
-        .map(|d| {
-            let bow_len: i32 = d.iter().fold(1, |acc, side| acc * side);
-            //println!("bow len: {:?}", bow_len);
-
-            let mut sorted_lens: Vec<i32> = d.clone();
-            sorted_lens.sort();
-            let ribbon_len = (sorted_lens[0] * 2) + (sorted_lens[1] * 2);
-            bow_len + ribbon_len
-        })
-        .collect::<Vec<i32>>();
+pub fn solve_part2(presents: &[Present]) -> Result<u128> {
+    checked_total(presents.iter().map(Present::ribbon), "ribbon")
+}
+
+aoc_common::embedded_input!();
 
-    lengths.iter().sum()
+pub const METADATA: Metadata = Metadata {
+    day: 2,
+    title: "I Was Told There Would Be No Math",
+    input: "One present per line as `LxWxH`",
+    part1: Answer {
+        label: "square feet of wrapping paper",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "feet of ribbon",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::Math, Tag::Parsing],
+};
+
+// Parsing already summed both totals, so the parts repeat sums known to fit
+const TOTALS_CHECKED_IN_PARSE: &str = "both totals were checked when the order was parsed";
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Present>;
+    type Part1 = u128;
+    type Part2 = u128;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    // Orders whose totals overflow are rejected here, so the parts can't fail
+    fn parse(input: &str) -> Result<Vec<Present>> {
+        let presents = parse(input)?;
+        solve_part1(&presents)?;
+        solve_part2(&presents)?;
+        Ok(presents)
+    }
+
+    fn part1(presents: &Vec<Present>) -> u128 {
+        solve_part1(presents).expect(TOTALS_CHECKED_IN_PARSE)
+    }
+
+    fn part2(presents: &Vec<Present>) -> u128 {
+        solve_part2(presents).expect(TOTALS_CHECKED_IN_PARSE)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn present(l: u32, w: u32, h: u32) -> Present {
+        Present { l, w, h }
+    }
+
     #[test]
     fn test_solve_part1() {
-        assert_eq!(solve_part1(&vec![vec![2, 3, 4]]), 58);
-        assert_eq!(solve_part1(&vec![vec![1, 1, 10]]), 43);
+        assert_eq!(solve_part1(&[present(2, 3, 4)]).unwrap(), 58);
+        assert_eq!(solve_part1(&[present(1, 1, 10)]).unwrap(), 43);
     }
 
     #[test]
     fn test_solve_part2() {
-        assert_eq!(solve_part2(&vec![vec![2, 3, 4]]), 34);
-        assert_eq!(solve_part2(&vec![vec![1, 1, 10]]), 14);
-        assert_eq!(solve_part2(&vec![vec![30, 22, 25]]), 16594)
+        assert_eq!(solve_part2(&[present(2, 3, 4)]).unwrap(), 34);
+        assert_eq!(solve_part2(&[present(1, 1, 10)]).unwrap(), 14);
+        assert_eq!(solve_part2(&[present(30, 22, 25)]).unwrap(), 16594)
+    }
+
+    #[test]
+    fn test_present() {
+        let gift = present(2, 3, 4);
+        assert_eq!(gift.sides(), [6, 12, 8]);
+        assert_eq!(gift.surface_area(), 52);
+        assert_eq!(gift.smallest_side(), 6);
+        assert_eq!(gift.volume(), 24);
+        assert_eq!(gift.paper(), 58);
+        assert_eq!(gift.ribbon(), 34);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("2x3x4\n1x1x10\n").unwrap(),
+            vec![present(2, 3, 4), present(1, 1, 10)]
+        );
+        assert_eq!(" 2x3x4\r".parse::<Present>().unwrap(), present(2, 3, 4));
+        assert_eq!(Present::new(2, 3, 4).unwrap(), present(2, 3, 4));
+        assert!(Present::new(0, 3, 4).is_err());
+
+        let error = |input: &str| format!("{:#}", parse(input).unwrap_err());
+        assert_eq!(
+            error("2x3x4\n2x3"),
+            "Line 2: Expected LxWxH, found 2 dimensions in \"2x3\""
+        );
+        assert_eq!(
+            error("2x3x4x5"),
+            "Line 1: Expected LxWxH, found 4 dimensions in \"2x3x4x5\""
+        );
+        assert_eq!(
+            error("1x1x1\n1x1x1\n2x0x4"),
+            "Line 3: Dimensions must be positive, found 0"
+        );
+        assert_eq!(
+            error("2x-3x4"),
+            "Line 1: Invalid dimension \"-3\": invalid digit found in string"
+        );
+        assert_eq!(
+            error("2x3x4\n\n"),
+            "Line 2: Invalid dimension \"\": cannot parse integer from empty string"
+        );
+    }
+
+    // Presents of up to u32::MAX feet a side, where i32 or u64 arithmetic would overflow
+    fn huge_presents(count: usize) -> impl Iterator<Item = Present> {
+        let mut state: u64 = 0x2015_0002;
+        (0..count).map(move |_| {
+            let mut dimension = || {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                u32::MAX - (state >> 40) as u32
+            };
+            Present {
+                l: dimension(),
+                w: dimension(),
+                h: dimension(),
+            }
+        })
+    }
+
+    #[test]
+    fn test_huge_presents() {
+        let largest = present(u32::MAX, u32::MAX, u32::MAX);
+        assert_eq!(largest.paper(), 129127208455837319175);
+        assert_eq!(largest.ribbon(), 79228162458924105402480066555);
+        // How many of the largest presents a ribbon total holds
+        assert_eq!(u128::MAX / largest.ribbon(), 4294967299);
+
+        let presents: Vec<Present> = huge_presents(1000).collect();
+        assert!(presents.iter().all(|p| p.volume() > u64::MAX as u128));
+        assert_eq!(solve_part1(&presents).unwrap(), 128611843497429275783256);
+        assert_eq!(
+            solve_part2(&presents).unwrap(),
+            78770742237962343500428497092613
+        );
+
+        let gigantic = "4294967296x1x1".parse::<Present>().unwrap_err();
+        assert!(format!("{:#}", gigantic).contains("number too large"));
+    }
+
+    #[test]
+    fn test_checked_total() {
+        assert_eq!(checked_total([1, 2, 3], "paper").unwrap(), 6);
+        assert_eq!(
+            checked_total([u128::MAX, 1], "ribbon")
+                .unwrap_err()
+                .to_string(),
+            "Total ribbon overflows u128"
+        );
+
+        // A little over 2^32 of the largest presents overflow the ribbon total
+        let ribbon = present(u32::MAX, u32::MAX, u32::MAX).ribbon();
+        assert_eq!(
+            checked_total([ribbon * 4294967295, ribbon * 4], "ribbon").unwrap(),
+            ribbon * 4294967299
+        );
+        assert!(checked_total([ribbon * 4294967295, ribbon * 5], "ribbon").is_err());
     }
 }
@@ -0,0 +1,124 @@
+//! Rules for how much paper and ribbon a present takes, to compare material
+//! costs under the elves' formulas and a few alternatives.
+
+use crate::{checked_total, Present};
+use anyhow::Result;
+
+pub trait WrappingPolicy {
+    // Name used to pick the policy on the command line
+    fn name(&self) -> &'static str;
+
+    // Square feet of wrapping paper, the elves' surface plus smallest side by default
+    fn paper(&self, present: &Present) -> u128 {
+        present.paper()
+    }
+
+    // Feet of ribbon, the elves' smallest perimeter plus a bow by default
+    fn ribbon(&self, present: &Present) -> u128 {
+        present.ribbon()
+    }
+}
+
+// The elves' rules from the puzzle
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Elves;
+
+impl WrappingPolicy for Elves {
+    fn name(&self) -> &'static str {
+        "elves"
+    }
+}
+
+// Paper cut to the exact surface, without slack
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoSlack;
+
+impl WrappingPolicy for NoSlack {
+    fn name(&self) -> &'static str {
+        "no-slack"
+    }
+
+    fn paper(&self, present: &Present) -> u128 {
+        present.surface_area()
+    }
+}
+
+// Generous slack: the largest side rather than the smallest
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LargestSlack;
+
+impl WrappingPolicy for LargestSlack {
+    fn name(&self) -> &'static str {
+        "largest-slack"
+    }
+
+    fn paper(&self, present: &Present) -> u128 {
+        let largest = present.sides().into_iter().max().unwrap_or(0);
+        present.surface_area() + largest
+    }
+}
+
+// Open-topped cubic gift bags fitting the present any way round, tied shut
+// around the neck with ribbon and no bow
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GiftBag;
+
+impl GiftBag {
+    fn side(present: &Present) -> u128 {
+        present.l.max(present.w).max(present.h) as u128
+    }
+}
+
+impl WrappingPolicy for GiftBag {
+    fn name(&self) -> &'static str {
+        "gift-bag"
+    }
+
+    fn paper(&self, present: &Present) -> u128 {
+        5 * GiftBag::side(present) * GiftBag::side(present)
+    }
+
+    fn ribbon(&self, present: &Present) -> u128 {
+        4 * GiftBag::side(present)
+    }
+}
+
+pub const POLICIES: &[&dyn WrappingPolicy] = &[&Elves, &NoSlack, &LargestSlack, &GiftBag];
+
+pub fn find_policy(name: &str) -> Option<&'static dyn WrappingPolicy> {
+    POLICIES
+        .iter()
+        .copied()
+        .find(|policy| policy.name() == name)
+}
+
+// Total paper and ribbon for an order under a policy
+pub fn materials(presents: &[Present], policy: &dyn WrappingPolicy) -> Result<(u128, u128)> {
+    let paper = checked_total(presents.iter().map(|p| policy.paper(p)), "paper")?;
+    let ribbon = checked_total(presents.iter().map(|p| policy.ribbon(p)), "ribbon")?;
+    Ok((paper, ribbon))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_policies() {
+        let presents = [Present { l: 2, w: 3, h: 4 }, Present { l: 1, w: 1, h: 10 }];
+        let materials = |name| materials(&presents, find_policy(name).unwrap()).unwrap();
+
+        assert_eq!(
+            materials("elves"),
+            (
+                solve_part1(&presents).unwrap(),
+                solve_part2(&presents).unwrap()
+            )
+        );
+        assert_eq!(materials("no-slack"), (52 + 42, 34 + 14));
+        assert_eq!(materials("largest-slack"), (52 + 12 + 42 + 10, 34 + 14));
+        assert_eq!(materials("gift-bag"), (5 * 16 + 5 * 100, 4 * 4 + 4 * 10));
+        assert!(find_policy("origami").is_none());
+    }
+}
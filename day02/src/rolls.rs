@@ -0,0 +1,488 @@
+//! How many rolls of wrapping paper an order takes.
+//!
+//! Each present is cut into its six faces plus a slack rectangle the size of
+//! its smallest side, and those pieces are laid out on rolls of a fixed
+//! width and length, in feet. Pieces can be turned a quarter, and must not
+//! overlap.
+
+use crate::Present;
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use anyhow::{anyhow, Result};
+
+// Orders up to two presents can be solved exactly
+pub const MAX_EXACT_PIECES: usize = 14;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Roll {
+    pub width: u32,
+    pub length: u32,
+}
+
+// A rectangle of paper, `width` across the roll and `length` along it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Piece {
+    pub width: u32,
+    pub length: u32,
+}
+
+impl Piece {
+    fn turned(self) -> Piece {
+        Piece {
+            width: self.length,
+            length: self.width,
+        }
+    }
+
+    fn area(self) -> u64 {
+        self.width as u64 * self.length as u64
+    }
+}
+
+// Where a piece is cut from: `x` across roll number `roll`, `y` along it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub roll: usize,
+    pub x: u32,
+    pub y: u32,
+    pub piece: Piece,
+}
+
+impl Placement {
+    fn overlaps(&self, other: &Placement) -> bool {
+        self.roll == other.roll
+            && self.x < other.x + other.piece.width
+            && other.x < self.x + self.piece.width
+            && self.y < other.y + other.piece.length
+            && other.y < self.y + self.piece.length
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    pub roll: Roll,
+    pub rolls: usize,
+    pub placements: Vec<Placement>,
+}
+
+impl Layout {
+    // Share of the bought paper that is not cut into pieces
+    pub fn waste_percent(&self) -> f64 {
+        let bought = self.rolls as f64 * self.roll.width as f64 * self.roll.length as f64;
+        if bought == 0.0 {
+            return 0.0;
+        }
+        let used: u64 = self.placements.iter().map(|p| p.piece.area()).sum();
+        100.0 * (1.0 - used as f64 / bought)
+    }
+}
+
+// The six faces of every present, then its slack
+pub fn pieces(presents: &[Present]) -> Vec<Piece> {
+    presents
+        .iter()
+        .flat_map(|present| {
+            let mut dimensions = [present.l, present.w, present.h];
+            dimensions.sort();
+            let [a, b, c] = dimensions;
+            let face = |width, length| Piece { width, length };
+            [
+                face(a, b),
+                face(a, b),
+                face(b, c),
+                face(b, c),
+                face(a, c),
+                face(a, c),
+                face(a, b),
+            ]
+        })
+        .collect()
+}
+
+// Long side across the roll when it fits, so shelves stay short
+fn orient(piece: Piece, roll: Roll) -> Result<Piece> {
+    let long = piece.width.max(piece.length);
+    let short = piece.width.min(piece.length);
+    let oriented = if long <= roll.width {
+        Piece {
+            width: long,
+            length: short,
+        }
+    } else {
+        Piece {
+            width: short,
+            length: long,
+        }
+    };
+    if oriented.width > roll.width || oriented.length > roll.length {
+        return Err(anyhow!(
+            "A {}x{} piece does not fit a {}x{} roll",
+            piece.width,
+            piece.length,
+            roll.width,
+            roll.length
+        ));
+    }
+    Ok(oriented)
+}
+
+struct Shelf {
+    roll: usize,
+    y: u32,
+    length: u32,
+    used_width: u32,
+}
+
+// First fit decreasing height: pieces from the longest down go on the first
+// shelf with room, or open a shelf on the first roll with length left
+pub fn shelf_pack(pieces: &[Piece], roll: Roll) -> Result<Layout> {
+    let mut oriented = pieces
+        .iter()
+        .map(|&piece| orient(piece, roll))
+        .collect::<Result<Vec<Piece>>>()?;
+    oriented.sort_by_key(|piece| core::cmp::Reverse((piece.length, piece.width)));
+
+    let mut shelves: Vec<Shelf> = Vec::new();
+    let mut used_lengths: Vec<u32> = Vec::new();
+    let mut placements = Vec::with_capacity(oriented.len());
+
+    for piece in oriented {
+        let shelf = shelves.iter_mut().find(|shelf| {
+            shelf.length >= piece.length && roll.width - shelf.used_width >= piece.width
+        });
+        let shelf = match shelf {
+            Some(shelf) => shelf,
+            None => {
+                let free_roll = used_lengths
+                    .iter()
+                    .position(|&used| roll.length - used >= piece.length);
+                let roll_idx = free_roll.unwrap_or_else(|| {
+                    used_lengths.push(0);
+                    used_lengths.len() - 1
+                });
+                shelves.push(Shelf {
+                    roll: roll_idx,
+                    y: used_lengths[roll_idx],
+                    length: piece.length,
+                    used_width: 0,
+                });
+                used_lengths[roll_idx] += piece.length;
+                shelves.last_mut().unwrap()
+            }
+        };
+        placements.push(Placement {
+            roll: shelf.roll,
+            x: shelf.used_width,
+            y: shelf.y,
+            piece,
+        });
+        shelf.used_width += piece.width;
+    }
+
+    Ok(Layout {
+        roll,
+        rolls: used_lengths.len(),
+        placements,
+    })
+}
+
+// Offsets that sums of piece sides can reach within `limit`: packings can be
+// pushed left and back until every piece sits at such an offset. Only the
+// sums are kept, so a long roll costs nothing more than a short one
+fn normal_patterns(pieces: &[Piece], limit: u32) -> Vec<u32> {
+    let mut reachable: BTreeSet<u32> = [0].into();
+    for piece in pieces {
+        let sides: BTreeSet<u32> = [piece.width, piece.length].into();
+        let previous = reachable.clone();
+        for side in sides.into_iter().filter(|&side| side <= limit) {
+            reachable.extend(previous.range(..=limit - side).map(|offset| offset + side));
+        }
+    }
+    reachable.into_iter().collect()
+}
+
+// Placements tried before the exact search gives up and keeps the greedy layout
+pub const MAX_EXACT_STEPS: usize = 2_000_000;
+
+struct Search<'a> {
+    pieces: &'a [Piece],
+    roll: Roll,
+    xs: Vec<u32>,
+    ys: Vec<u32>,
+    placements: Vec<Placement>,
+    // Area of pieces `n..` for every n, to prune once they can't fit the space left
+    remaining_area: Vec<u64>,
+    steps: usize,
+}
+
+impl Search<'_> {
+    // Place pieces `next..` on at most `rolls` rolls, opening them in order.
+    // None when the step budget runs out
+    fn place(&mut self, next: usize, rolls: usize, opened: usize) -> Option<bool> {
+        let Some(&piece) = self.pieces.get(next) else {
+            return Some(true);
+        };
+        let used: u64 = self.placements.iter().map(|p| p.piece.area()).sum();
+        let capacity = rolls as u64 * self.roll.width as u64 * self.roll.length as u64;
+        if self.remaining_area[next] > capacity - used {
+            return Some(false);
+        }
+        // A piece like the one before goes after it, so swapping them isn't tried again
+        let earliest = match next.checked_sub(1) {
+            Some(previous) if self.pieces[previous] == piece => {
+                let p = self.placements[previous];
+                (p.roll, p.y, p.x)
+            }
+            _ => (0, 0, 0),
+        };
+        let turns = if piece.width == piece.length {
+            vec![piece]
+        } else {
+            vec![piece, piece.turned()]
+        };
+
+        for roll in earliest.0..rolls.min(opened + 1) {
+            for &piece in &turns {
+                let Piece { width, length } = piece;
+                for yi in 0..self.ys.len() {
+                    let y = self.ys[yi];
+                    if y.checked_add(length)
+                        .is_none_or(|end| end > self.roll.length)
+                    {
+                        break;
+                    }
+                    for xi in 0..self.xs.len() {
+                        let x = self.xs[xi];
+                        if x.checked_add(width).is_none_or(|end| end > self.roll.width) {
+                            break;
+                        }
+                        if (roll, y, x) < earliest {
+                            continue;
+                        }
+                        self.steps += 1;
+                        if self.steps > MAX_EXACT_STEPS {
+                            return None;
+                        }
+                        let placement = Placement { roll, x, y, piece };
+                        if self.placements.iter().any(|p| p.overlaps(&placement)) {
+                            continue;
+                        }
+                        self.placements.push(placement);
+                        if self.place(next + 1, rolls, opened.max(roll + 1))? {
+                            return Some(true);
+                        }
+                        self.placements.pop();
+                    }
+                }
+            }
+        }
+        Some(false)
+    }
+}
+
+// Fewest rolls possible, by backtracking over placements on as many rolls as
+// the area needs, then one more at a time up to the greedy layout's count.
+// Past `MAX_EXACT_STEPS` placements the greedy layout is kept
+pub fn exact_pack(pieces: &[Piece], roll: Roll) -> Result<Layout> {
+    if pieces.len() > MAX_EXACT_PIECES {
+        return Err(anyhow!(
+            "{} pieces are too many to pack exactly, the limit is {}",
+            pieces.len(),
+            MAX_EXACT_PIECES
+        ));
+    }
+    let greedy = shelf_pack(pieces, roll)?;
+
+    // Largest first, with identical pieces next to each other
+    let mut sorted = pieces.to_vec();
+    sorted.sort_by_key(|piece| core::cmp::Reverse((piece.area(), piece.width, piece.length)));
+    let area: u64 = sorted.iter().map(|piece| piece.area()).sum();
+    let lower_bound = area.div_ceil(roll.width as u64 * roll.length as u64) as usize;
+    let mut remaining_area = vec![0; sorted.len() + 1];
+    for idx in (0..sorted.len()).rev() {
+        remaining_area[idx] = remaining_area[idx + 1] + sorted[idx].area();
+    }
+
+    let mut search = Search {
+        pieces: &sorted,
+        roll,
+        xs: normal_patterns(&sorted, roll.width),
+        ys: normal_patterns(&sorted, roll.length),
+        placements: Vec::with_capacity(sorted.len()),
+        remaining_area,
+        steps: 0,
+    };
+    for rolls in lower_bound..greedy.rolls {
+        match search.place(0, rolls, 0) {
+            Some(true) => {
+                return Ok(Layout {
+                    roll,
+                    rolls,
+                    placements: search.placements,
+                })
+            }
+            Some(false) => {}
+            None => break,
+        }
+    }
+    Ok(greedy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(layout: &Layout, pieces: &[Piece]) {
+        assert_eq!(layout.placements.len(), pieces.len());
+        for (i, a) in layout.placements.iter().enumerate() {
+            assert!(a.roll < layout.rolls);
+            assert!(a.x + a.piece.width <= layout.roll.width);
+            assert!(a.y + a.piece.length <= layout.roll.length);
+            assert!(layout.placements[i + 1..].iter().all(|b| !a.overlaps(b)));
+        }
+        let mut placed: Vec<(u32, u32)> = layout
+            .placements
+            .iter()
+            .map(|p| {
+                (
+                    p.piece.width.min(p.piece.length),
+                    p.piece.width.max(p.piece.length),
+                )
+            })
+            .collect();
+        let mut expected: Vec<(u32, u32)> = pieces
+            .iter()
+            .map(|p| (p.width.min(p.length), p.width.max(p.length)))
+            .collect();
+        placed.sort();
+        expected.sort();
+        assert_eq!(placed, expected);
+    }
+
+    #[test]
+    fn test_pieces() {
+        let cuts = pieces(&[Present { l: 2, w: 3, h: 4 }]);
+        assert_eq!(cuts.len(), 7);
+        let area: u64 = cuts.iter().map(|p| p.area()).sum();
+        assert_eq!(area as u128, Present { l: 2, w: 3, h: 4 }.paper());
+    }
+
+    #[test]
+    fn test_shelf_pack() {
+        let presents = [Present { l: 2, w: 3, h: 4 }, Present { l: 1, w: 1, h: 10 }];
+        let cuts = pieces(&presents);
+        let roll = Roll {
+            width: 12,
+            length: 12,
+        };
+        let layout = shelf_pack(&cuts, roll).unwrap();
+        check(&layout, &cuts);
+        assert_eq!(layout.rolls, 1);
+        assert!((layout.waste_percent() - 100.0 * (1.0 - 101.0 / 144.0)).abs() < 1e-9);
+
+        let narrow = Roll {
+            width: 3,
+            length: 9,
+        };
+        let error = shelf_pack(&cuts, narrow).unwrap_err();
+        assert_eq!(error.to_string(), "A 1x10 piece does not fit a 3x9 roll");
+    }
+
+    #[test]
+    fn test_exact_pack() {
+        // Shelves waste the space beside the 3x2 piece, a 3x4 roll holds both exactly
+        let cuts = [
+            Piece {
+                width: 3,
+                length: 2,
+            },
+            Piece {
+                width: 2,
+                length: 2,
+            },
+            Piece {
+                width: 1,
+                length: 2,
+            },
+        ];
+        let roll = Roll {
+            width: 3,
+            length: 4,
+        };
+        assert_eq!(shelf_pack(&cuts, roll).unwrap().rolls, 2);
+        let layout = exact_pack(&cuts, roll).unwrap();
+        check(&layout, &cuts);
+        assert_eq!(layout.rolls, 1);
+        assert_eq!(layout.waste_percent(), 0.0);
+
+        // The 58 square feet of a 2x3x4 present fit a 6x10 roll, but not in shelves
+        let cuts = pieces(&[Present { l: 2, w: 3, h: 4 }]);
+        let roll = Roll {
+            width: 6,
+            length: 10,
+        };
+        assert_eq!(shelf_pack(&cuts, roll).unwrap().rolls, 2);
+        let layout = exact_pack(&cuts, roll).unwrap();
+        check(&layout, &cuts);
+        assert_eq!(layout.rolls, 1);
+
+        let order = [Present { l: 1, w: 1, h: 1 }; 3];
+        assert!(exact_pack(&pieces(&order), roll).is_err());
+
+        // The 1x8 faces only fit along the roll
+        let cuts = pieces(&[Present { l: 1, w: 1, h: 8 }]);
+        let layout = exact_pack(&cuts, roll).unwrap();
+        check(&layout, &cuts);
+        assert_eq!(layout.rolls, 1);
+
+        let cuts = pieces(&[Present { l: 2, w: 3, h: 4 }]);
+        let long = Roll {
+            width: 3,
+            length: 4_000_000_000,
+        };
+        let layout = exact_pack(&cuts, long).unwrap();
+        check(&layout, &cuts);
+        assert_eq!(layout.rolls, 1);
+        // Fourteen pieces in seven identical pairs, greedy needs 3 rolls
+        let cuts = pieces(&[Present { l: 5, w: 7, h: 9 }, Present { l: 6, w: 8, h: 10 }]);
+        let roll = Roll {
+            width: 20,
+            length: 20,
+        };
+        assert_eq!(cuts.len(), MAX_EXACT_PIECES);
+        assert_eq!(shelf_pack(&cuts, roll).unwrap().rolls, 3);
+        let layout = exact_pack(&cuts, roll).unwrap();
+        check(&layout, &cuts);
+        assert!(layout.rolls <= 3);
+
+        let huge = [Piece {
+            width: u32::MAX,
+            length: u32::MAX - 1,
+        }];
+        let roll = Roll {
+            width: u32::MAX,
+            length: u32::MAX,
+        };
+        assert_eq!(exact_pack(&huge, roll).unwrap().rolls, 1);
+    }
+
+    #[test]
+    fn test_normal_patterns() {
+        let cuts = [
+            Piece {
+                width: 2,
+                length: 3,
+            },
+            Piece {
+                width: 8,
+                length: 1,
+            },
+        ];
+        assert_eq!(normal_patterns(&cuts, 6), vec![0, 1, 2, 3, 4]);
+        assert_eq!(
+            normal_patterns(&cuts, u32::MAX),
+            vec![0, 1, 2, 3, 4, 8, 10, 11]
+        );
+    }
+}
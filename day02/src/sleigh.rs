@@ -0,0 +1,300 @@
+//! Loading the presents into sleighs.
+//!
+//! Presents are packed from the largest down, each at the first free corner
+//! (lowest, then furthest back, then leftmost) of the first sleigh it fits
+//! in, turned any of its six ways. A new sleigh is brought in when no loaded
+//! one has room.
+
+use crate::Present;
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use anyhow::{anyhow, Result};
+use core::fmt::Write;
+use core::str::FromStr;
+
+// Cargo space of a sleigh: `length` along x, `width` along y, `height` along z
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sleigh {
+    pub length: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FromStr for Sleigh {
+    type Err = anyhow::Error;
+
+    // `LxWxH`, like a present
+    fn from_str(s: &str) -> Result<Self> {
+        let Present { l, w, h } = s.parse()?;
+        Ok(Sleigh {
+            length: l,
+            width: w,
+            height: h,
+        })
+    }
+}
+
+impl Sleigh {
+    fn size(&self) -> [u64; 3] {
+        [self.length as u64, self.width as u64, self.height as u64]
+    }
+}
+
+// Where present number `present` of the order went, turned to `size`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub present: usize,
+    pub sleigh: usize,
+    pub position: [u64; 3],
+    pub size: [u64; 3],
+}
+
+impl Placement {
+    fn overlaps(&self, position: [u64; 3], size: [u64; 3]) -> bool {
+        (0..3).all(|axis| {
+            self.position[axis] < position[axis] + size[axis]
+                && position[axis] < self.position[axis] + self.size[axis]
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Loading {
+    pub sleigh: Sleigh,
+    pub sleighs: usize,
+    pub placements: Vec<Placement>,
+}
+
+// The distinct ways a present can be turned
+fn rotations(present: &Present) -> BTreeSet<[u64; 3]> {
+    let [l, w, h] = [present.l as u64, present.w as u64, present.h as u64];
+    [
+        [l, w, h],
+        [l, h, w],
+        [w, l, h],
+        [w, h, l],
+        [h, l, w],
+        [h, w, l],
+    ]
+    .into()
+}
+
+// A loaded sleigh: its presents and the corners where the next could go,
+// ordered by height, depth then offset
+struct Hold {
+    placements: Vec<Placement>,
+    corners: BTreeSet<[u64; 3]>,
+}
+
+impl Hold {
+    fn new() -> Self {
+        Hold {
+            placements: Vec::new(),
+            corners: [[0, 0, 0]].into(),
+        }
+    }
+
+    fn fit(&self, sleigh: Sleigh, rotations: &BTreeSet<[u64; 3]>) -> Option<([u64; 3], [u64; 3])> {
+        let limits = sleigh.size();
+        self.corners.iter().find_map(|&[z, y, x]| {
+            let position = [x, y, z];
+            rotations.iter().copied().find_map(|size| {
+                let inside = (0..3).all(|axis| position[axis] + size[axis] <= limits[axis]);
+                let free = || self.placements.iter().all(|p| !p.overlaps(position, size));
+                (inside && free()).then_some((position, size))
+            })
+        })
+    }
+
+    fn place(&mut self, placement: Placement) {
+        let [x, y, z] = placement.position;
+        let [dx, dy, dz] = placement.size;
+        self.corners.remove(&[z, y, x]);
+        self.corners
+            .extend([[z, y, x + dx], [z, y + dy, x], [z + dz, y, x]]);
+        self.placements.push(placement);
+    }
+}
+
+pub fn load(presents: &[Present], sleigh: Sleigh) -> Result<Loading> {
+    let mut order: Vec<usize> = (0..presents.len()).collect();
+    order.sort_by_key(|&idx| core::cmp::Reverse(presents[idx].volume()));
+
+    let mut holds: Vec<Hold> = Vec::new();
+    for idx in order {
+        let rotations = rotations(&presents[idx]);
+        let found = holds
+            .iter()
+            .enumerate()
+            .find_map(|(number, hold)| Some((number, hold.fit(sleigh, &rotations)?)));
+        let (number, (position, size)) = match found {
+            Some(found) => found,
+            None => {
+                let hold = Hold::new();
+                let Present { l, w, h } = presents[idx];
+                let fit = hold.fit(sleigh, &rotations).ok_or_else(|| {
+                    anyhow!(
+                        "Present {} ({}x{}x{}) does not fit a {}x{}x{} sleigh",
+                        idx + 1,
+                        l,
+                        w,
+                        h,
+                        sleigh.length,
+                        sleigh.width,
+                        sleigh.height
+                    )
+                })?;
+                holds.push(hold);
+                (holds.len() - 1, fit)
+            }
+        };
+        holds[number].place(Placement {
+            present: idx,
+            sleigh: number,
+            position,
+            size,
+        });
+    }
+
+    let mut placements: Vec<Placement> = holds.into_iter().flat_map(|h| h.placements).collect();
+    placements.sort_by_key(|p| p.present);
+    Ok(Loading {
+        sleigh,
+        sleighs: placements.iter().map(|p| p.sleigh + 1).max().unwrap_or(0),
+        placements,
+    })
+}
+
+// The 8 corners of a box, bit 0 choosing x, bit 1 y and bit 2 z
+fn corners(position: [u64; 3], size: [u64; 3]) -> [[u64; 3]; 8] {
+    core::array::from_fn(|bits| {
+        core::array::from_fn(|axis| position[axis] + size[axis] * ((bits >> axis) & 1) as u64)
+    })
+}
+
+impl Loading {
+    // Wavefront OBJ mesh: an object per sleigh outline (edges only) and a box
+    // per present, sleighs side by side along x with a sleigh's width between them
+    pub fn to_obj(&self) -> String {
+        let size = self.sleigh.size();
+        let spacing = size[0] + size[1];
+        let mut obj = String::from("# Sleigh loading, units in feet\n");
+        let mut vertices = 0;
+
+        let mut write_box = |obj: &mut String, name: String, position: [u64; 3], dims| {
+            let _ = writeln!(obj, "o {}", name);
+            for [x, y, z] in corners(position, dims) {
+                let _ = writeln!(obj, "v {} {} {}", x, y, z);
+            }
+            let base = vertices + 1;
+            vertices += 8;
+            base
+        };
+
+        for sleigh in 0..self.sleighs {
+            let offset = [sleigh as u64 * spacing, 0, 0];
+            let base = write_box(&mut obj, format!("sleigh_{}", sleigh + 1), offset, size);
+            // Edges join corners differing in a single bit
+            for from in 0..8 {
+                for bit in [1, 2, 4] {
+                    if from & bit == 0 {
+                        let _ = writeln!(obj, "l {} {}", base + from, base + (from | bit));
+                    }
+                }
+            }
+        }
+
+        // Faces hold one axis at 0 or 1, corners listed counter-clockwise from outside
+        const FACES: [[usize; 4]; 6] = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ];
+        for placement in &self.placements {
+            let mut position = placement.position;
+            position[0] += placement.sleigh as u64 * spacing;
+            let name = format!("present_{}", placement.present + 1);
+            let base = write_box(&mut obj, name, position, placement.size);
+            for face in FACES {
+                let [a, b, c, d] = face.map(|corner| base + corner);
+                let _ = writeln!(obj, "f {} {} {} {}", a, b, c, d);
+            }
+        }
+        obj
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn check(loading: &Loading, presents: &[Present]) {
+        assert_eq!(loading.placements.len(), presents.len());
+        for (i, a) in loading.placements.iter().enumerate() {
+            assert_eq!(a.present, i);
+            assert!(a.sleigh < loading.sleighs);
+            assert!(
+                (0..3).all(|axis| a.position[axis] + a.size[axis] <= loading.sleigh.size()[axis])
+            );
+            assert!(rotations(&presents[i]).contains(&a.size));
+            assert!(loading.placements[i + 1..]
+                .iter()
+                .all(|b| a.sleigh != b.sleigh || !b.overlaps(a.position, a.size)));
+        }
+    }
+
+    #[test]
+    fn test_load() {
+        let sleigh: Sleigh = "4x4x2".parse().unwrap();
+        let presents = [
+            Present { l: 2, w: 2, h: 2 },
+            Present { l: 1, w: 1, h: 4 },
+            Present { l: 2, w: 2, h: 2 },
+            Present { l: 4, w: 2, h: 2 },
+            Present { l: 2, w: 2, h: 1 },
+        ];
+        let loading = load(&presents, sleigh).unwrap();
+        check(&loading, &presents);
+        // The 4x2x2 and both cubes fill the first sleigh's 32 cubic feet
+        assert_eq!(loading.sleighs, 2);
+        let sleighs: Vec<usize> = loading.placements.iter().map(|p| p.sleigh).collect();
+        assert_eq!(sleighs, vec![0, 1, 0, 0, 1]);
+        assert_eq!(loading.placements[3].position, [0, 0, 0]);
+        assert_eq!(loading.placements[3].size, [2, 4, 2]);
+        // Only fits lying down
+        assert_eq!(loading.placements[1].size[2], 1);
+
+        let many = [Present { l: 2, w: 2, h: 2 }; 9];
+        let loading = load(&many, sleigh).unwrap();
+        check(&loading, &many);
+        // Four to a sleigh
+        assert_eq!(loading.sleighs, 3);
+
+        let error = load(&[Present { l: 5, w: 1, h: 1 }], sleigh).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Present 1 (5x1x1) does not fit a 4x4x2 sleigh"
+        );
+        assert!("4x0x2".parse::<Sleigh>().is_err());
+    }
+
+    #[test]
+    fn test_to_obj() {
+        let sleigh: Sleigh = "2x1x1".parse().unwrap();
+        let presents = [Present { l: 1, w: 1, h: 1 }; 3];
+        let obj = load(&presents, sleigh).unwrap().to_obj();
+        let count = |prefix: &str| obj.lines().filter(|l| l.starts_with(prefix)).count();
+        assert_eq!(count("o "), 2 + 3);
+        assert_eq!(count("v "), 8 * 5);
+        assert_eq!(count("l "), 12 * 2);
+        assert_eq!(count("f "), 6 * 3);
+        // The third present sits at the start of the second sleigh, 3 feet along
+        assert!(obj.contains("o present_3\nv 3 0 0\nv 4 0 0\nv 3 1 0\nv 4 1 0\nv 3 0 1\n"));
+    }
+}
@@ -1,96 +1,95 @@
-use anyhow::{Context, Result};
-use std::{collections::HashSet, fs::read_to_string, i32};
-
-pub fn parse_input(input_path: String) -> Result<Vec<char>, anyhow::Error> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
-    let directions: Vec<char> = input_contents.chars().collect();
-
-    Ok(directions)
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::BTreeSet;
+use alloc::vec;
+use alloc::vec::Vec;
+use anyhow::{anyhow, Result};
+use aoc_common::{Answer, AnswerKind, Metadata, Solution, Tag};
+
+// Anything but the four arrows is rejected, apart from the line ending the input file
+pub fn parse(input_contents: &str) -> Result<Vec<char>, anyhow::Error> {
+    let input = input_contents.trim_end();
+    if let Some((offset, found)) = input
+        .char_indices()
+        .find(|(_, c)| !matches!(c, '^' | 'v' | '<' | '>'))
+    {
+        return Err(anyhow!(
+            "Invalid direction {:?} at byte {}, expected one of ^v<>",
+            found,
+            offset
+        ));
+    }
+    Ok(input.chars().collect())
 }
 
-pub fn solve_part1(directions: &Vec<char>) -> i32 {
-    let mut visited_houses: HashSet<(i32, i32)> = HashSet::new();
+// Houses visited when `santas` deliverers take turns following the directions
+pub fn visited_houses(directions: &[char], santas: usize) -> BTreeSet<(i32, i32)> {
+    let mut positions: Vec<(i32, i32)> = vec![(0, 0); santas];
+    let mut visited_houses: BTreeSet<(i32, i32)> = BTreeSet::new();
+    visited_houses.insert((0, 0));
 
-    let mut x: i32 = 0;
-    let mut y: i32 = 0;
-    let house_zero: (i32, i32) = (x, y);
-    visited_houses.insert(house_zero);
-
-    directions.iter().for_each(|&c| {
+    for (index, &c) in directions.iter().enumerate() {
+        let (x, y) = &mut positions[index % santas];
         match c {
-            '^' => y += 1,
-            '<' => x -= 1,
-            '>' => x += 1,
-            'v' => y -= 1,
+            '^' => *y += 1,
+            '<' => *x -= 1,
+            '>' => *x += 1,
+            'v' => *y -= 1,
             _ => panic!("Unknown direction panic: {}", c),
         };
-        visited_houses.insert((x, y));
-    });
+        visited_houses.insert((*x, *y));
+    }
 
-    visited_houses.len() as i32
+    visited_houses
 }
 
-pub fn solve_part2(directions: &Vec<char>) -> i32 {
-    let mut visited_houses: HashSet<(i32, i32)> = HashSet::new();
-
-    let mut santa_x: i32 = 0;
-    let mut santa_y: i32 = 0;
-
-    let mut robo_x: i32 = 0;
-    let mut robo_y: i32 = 0;
-
-    let santa_zero: (i32, i32) = (santa_x, santa_y);
-    visited_houses.insert(santa_zero);
+pub fn solve_part1(directions: &[char]) -> i32 {
+    visited_houses(directions, 1).len() as i32
+}
 
-    for (index, c) in directions.iter().enumerate() {
-        let is_santa: bool = index % 2 == 0;
+pub fn solve_part2(directions: &[char]) -> i32 {
+    visited_houses(directions, 2).len() as i32
+}
 
-        match c {
-            '^' => {
-                if is_santa {
-                    santa_y += 1
-                } else {
-                    robo_y += 1
-                }
-            }
-            '<' => {
-                if is_santa {
-                    santa_x -= 1
-                } else {
-                    robo_x -= 1
-                }
-            }
-            '>' => {
-                if is_santa {
-                    santa_x += 1
-                } else {
-                    robo_x += 1
-                }
-            }
-            'v' => {
-                if is_santa {
-                    santa_y -= 1
-                } else {
-                    robo_y -= 1
-                }
-            }
-            _ => panic!("Unknown direction panic!"),
-        };
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 3,
+    title: "Perfectly Spherical Houses in a Vacuum",
+    input: "A single line of `^`, `v`, `<` and `>` moves",
+    part1: Answer {
+        label: "houses visited by Santa",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "houses visited by Santa and Robo-Santa",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::Grid, Tag::Simulation],
+};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<char>;
+    type Part1 = i32;
+    type Part2 = i32;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> Result<Vec<char>> {
+        parse(input)
+    }
 
-        let house: (i32, i32) = if is_santa {
-            (santa_x, santa_y)
-        } else {
-            (robo_x, robo_y)
-        };
-        // println!(
-        //     "{} going to {:?}",
-        //     if is_santa { "Santa" } else { "Robo" },
-        //     house
-        // );
-        visited_houses.insert(house);
+    fn part1(directions: &Vec<char>) -> i32 {
+        solve_part1(directions)
     }
 
-    visited_houses.len() as i32
+    fn part2(directions: &Vec<char>) -> i32 {
+        solve_part2(directions)
+    }
 }
 
 #[cfg(test)]
@@ -126,4 +125,21 @@ mod tests {
         let input3 = "^v^v^v^v^v".chars().collect::<Vec<char>>();
         assert_eq!(solve_part2(&input3), 11);
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("^v\n").unwrap(), vec!['^', 'v']);
+        let error = parse("^>x<").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid direction 'x' at byte 2, expected one of ^v<>"
+        );
+    }
+
+    #[test]
+    fn test_visited_houses_snapshot() {
+        let directions: Vec<char> = "^v^v^v^v^v".chars().collect();
+        insta::assert_debug_snapshot!("santa", visited_houses(&directions, 1));
+        insta::assert_debug_snapshot!("santa_and_robo", visited_houses(&directions, 2));
+    }
 }
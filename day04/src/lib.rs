@@ -1,37 +1,88 @@
-use anyhow::{Context, Result};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use anyhow::Result;
+use aoc_common::{Answer, AnswerKind, Metadata, Solution, Tag};
 use md5::{Digest, Md5};
-use std::{fs::read_to_string, i32};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub fn parse(input_contents: &str) -> Result<String, anyhow::Error> {
+    Ok(input_contents.to_string())
+}
 
-pub fn parse_input(input_path: String) -> Result<String, anyhow::Error> {
-    let secret_key: String = read_to_string(input_path).context("Could not read input file")?;
+fn is_match(secret_key: &str, n: i32, prefix: &str) -> bool {
+    let candidate = format!("{}{}", secret_key, n);
+    let hash = format!("{:x}", Md5::digest(candidate.as_bytes()));
+    hash.starts_with(prefix)
+}
 
-    Ok(secret_key)
+// Lowest nonce below `limit` whose hash starts with `prefix`
+#[cfg(not(feature = "parallel"))]
+fn find_nonce(secret_key: &str, prefix: &str, limit: i32) -> Option<i32> {
+    (0..limit).find(|&n| is_match(secret_key, n, prefix))
 }
 
-pub fn solve_part1(secret_key: &String) -> i32 {
-    for n in 0..100_000_000 {
-        let candidate = format!("{}{}", secret_key, n);
-        let hash = format!("{:x}", Md5::digest(candidate.as_bytes()));
-        println!("{}", hash);
+// Blocks of nonces are scanned one after another, each spread over all cores.
+// Taking the first match of the first block that has one keeps the answer the lowest nonce.
+#[cfg(feature = "parallel")]
+fn find_nonce(secret_key: &str, prefix: &str, limit: i32) -> Option<i32> {
+    const BLOCK: i32 = 100_000;
+    (0..limit).step_by(BLOCK as usize).find_map(|start| {
+        (start..start.saturating_add(BLOCK).min(limit))
+            .into_par_iter()
+            .find_first(|&n| is_match(secret_key, n, prefix))
+    })
+}
 
-        if hash.starts_with("00000") {
-            return n;
-        }
-    }
-    0
+pub fn solve_part1(secret_key: &str) -> i32 {
+    find_nonce(secret_key, "00000", 100_000_000).unwrap_or(0)
+}
+
+pub fn solve_part2(secret_key: &str) -> i32 {
+    find_nonce(secret_key, "000000", i32::MAX).unwrap_or(0)
 }
 
-pub fn solve_part2(secret_key: &String) -> i32 {
-    for n in 0..i32::MAX {
-        let candidate = format!("{}{}", secret_key, n);
-        let hash = format!("{:x}", Md5::digest(candidate.as_bytes()));
-        println!("{}", hash);
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 4,
+    title: "The Ideal Stocking Stuffer",
+    input: "A single line holding the secret key",
+    part1: Answer {
+        label: "lowest number giving five leading zeros",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "lowest number giving six leading zeros",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::BruteForce, Tag::Hashing],
+};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = String;
+    type Part1 = i32;
+    type Part2 = i32;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> Result<String> {
+        parse(input)
+    }
+
+    fn part1(secret_key: &String) -> i32 {
+        solve_part1(secret_key)
+    }
 
-        if hash.starts_with("000000") {
-            return n;
-        }
+    fn part2(secret_key: &String) -> i32 {
+        solve_part2(secret_key)
     }
-    0
 }
 
 #[cfg(test)]
@@ -40,7 +91,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(solve_part1(&"abcdef".to_string()), 609043);
-        assert_eq!(solve_part1(&"pqrstuv".to_string()), 1048970);
+        assert_eq!(solve_part1("abcdef"), 609043);
+        assert_eq!(solve_part1("pqrstuv"), 1048970);
     }
 }
@@ -1,9 +1,15 @@
-use anyhow::{Context, Result};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use anyhow::Result;
+use aoc_common::{Answer, AnswerKind, Explain, Metadata, Part, Solution, Tag};
 use regex::Regex;
-use std::fs::read_to_string;
 
-pub fn parse_input(input_path: String) -> Result<Vec<String>, anyhow::Error> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
+pub fn parse(input_contents: &str) -> Result<Vec<String>, anyhow::Error> {
     let strings: Vec<String> = input_contents.lines().map(String::from).collect();
 
     Ok(strings)
@@ -13,10 +19,16 @@ fn has_doubles(s: &str) -> bool {
     s.chars().zip(s.chars().skip(1)).any(|(a, b)| a == b)
 }
 
-pub fn solve_part1(strings: &Vec<String>) -> usize {
+// Regexes for the vowel and banned pair rules of part 1
+fn part1_regexes() -> (Regex, Regex) {
     let vowels_trio = Regex::new("^(.*[aeiou]){3,}.*$").unwrap();
     //let double_pairs = Regex::new(r"(.)\1").unwrap(); // \1 not supported
     let banned_pairs = Regex::new(r"ab|cd|pq|xy").unwrap();
+    (vowels_trio, banned_pairs)
+}
+
+pub fn solve_part1(strings: &[String]) -> usize {
+    let (vowels_trio, banned_pairs) = part1_regexes();
 
     strings
         .iter()
@@ -47,13 +59,102 @@ fn has_letter_sandwich(s: &str) -> bool {
 }
 
 // TODO: regex would need backreferences which are not supported
-pub fn solve_part2(strings: &Vec<String>) -> usize {
+pub fn solve_part2(strings: &[String]) -> usize {
     strings
         .iter()
         .filter(|&s| has_repeated_pair(s) && has_letter_sandwich(s))
         .count()
 }
 
+// Describe every part 1 rule a string breaks
+fn part1_failures(s: &str, vowels_trio: &Regex, banned_pairs: &Regex) -> Vec<&'static str> {
+    let mut failures = Vec::new();
+    if !vowels_trio.is_match(s) {
+        failures.push("fewer than three vowels");
+    }
+    if !has_doubles(s) {
+        failures.push("no letter twice in a row");
+    }
+    if banned_pairs.is_match(s) {
+        failures.push("contains ab, cd, pq or xy");
+    }
+    failures
+}
+
+// Describe every part 2 rule a string breaks
+fn part2_failures(s: &str) -> Vec<&'static str> {
+    let mut failures = Vec::new();
+    if !has_repeated_pair(s) {
+        failures.push("no pair appearing twice");
+    }
+    if !has_letter_sandwich(s) {
+        failures.push("no letter repeating with one between");
+    }
+    failures
+}
+
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 5,
+    title: "Doesn't He Have Intern-Elves For This?",
+    input: "One string per line",
+    part1: Answer {
+        label: "nice strings under the first rules",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "nice strings under the new rules",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::Strings],
+};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        parse(input)
+    }
+
+    fn part1(strings: &Vec<String>) -> usize {
+        solve_part1(strings)
+    }
+
+    fn part2(strings: &Vec<String>) -> usize {
+        solve_part2(strings)
+    }
+}
+
+impl Explain for Day05 {
+    fn explain(strings: &Vec<String>, part: Part) -> String {
+        let (vowels_trio, banned_pairs) = part1_regexes();
+        let mut nice = 0;
+        let mut lines = Vec::new();
+
+        for s in strings {
+            let failures = match part {
+                Part::One => part1_failures(s, &vowels_trio, &banned_pairs),
+                Part::Two => part2_failures(s),
+            };
+            if failures.is_empty() {
+                nice += 1;
+                lines.push(format!("{}: nice", s));
+            } else {
+                lines.push(format!("{}: naughty ({})", s, failures.join(", ")));
+            }
+        }
+        lines.push(format!("{} of {} strings are nice", nice, strings.len()));
+        lines.join("\n")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,11 +174,11 @@ mod tests {
 
     #[test]
     fn test_individual_strings() {
-        assert_eq!(solve_part1(&vec!["ugknbfddgicrmopn".to_string()]), 1);
-        assert_eq!(solve_part1(&vec!["aaa".to_string()]), 1);
-        assert_eq!(solve_part1(&vec!["jchzalrnumimnmhp".to_string()]), 0);
-        assert_eq!(solve_part1(&vec!["haegwjzuvuyypxyu".to_string()]), 0);
-        assert_eq!(solve_part1(&vec!["dvszwmarrgswjxmb".to_string()]), 0);
+        assert_eq!(solve_part1(&["ugknbfddgicrmopn".to_string()]), 1);
+        assert_eq!(solve_part1(&["aaa".to_string()]), 1);
+        assert_eq!(solve_part1(&["jchzalrnumimnmhp".to_string()]), 0);
+        assert_eq!(solve_part1(&["haegwjzuvuyypxyu".to_string()]), 0);
+        assert_eq!(solve_part1(&["dvszwmarrgswjxmb".to_string()]), 0);
     }
 
     #[test]
@@ -90,4 +191,24 @@ mod tests {
         ];
         assert_eq!(solve_part2(&input), 2);
     }
+    #[test]
+    fn test_explain() {
+        let input = vec![
+            "ugknbfddgicrmopn".to_string(),
+            "dvszwmarrgswjxmb".to_string(),
+        ];
+        assert_eq!(
+            Day05::explain(&input, Part::One),
+            "ugknbfddgicrmopn: nice\n\
+             dvszwmarrgswjxmb: naughty (fewer than three vowels)\n\
+             1 of 2 strings are nice"
+        );
+
+        let input = vec!["ieodomkazucvgmuy".to_string()];
+        assert_eq!(
+            Day05::explain(&input, Part::Two),
+            "ieodomkazucvgmuy: naughty (no pair appearing twice)\n\
+             0 of 1 strings are nice"
+        );
+    }
 }
@@ -1,8 +1,24 @@
-use anyhow::{Context, Result};
-use std::fs::read_to_string;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub fn parse_input(input_path: String) -> Result<Vec<String>, anyhow::Error> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use anyhow::{anyhow, Context, Result};
+use aoc_common::{Answer, AnswerKind, Metadata, Part, Solution, Tag};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Side of the square grid of lights
+const GRID_SIZE: usize = 1000;
+
+// Every line must be a valid instruction
+pub fn parse(input_contents: &str) -> Result<Vec<String>, anyhow::Error> {
+    for (idx, line) in input_contents.lines().enumerate() {
+        Instruction::from_str(line).with_context(|| format!("Line {}", idx + 1))?;
+    }
     let strings: Vec<String> = input_contents.lines().map(String::from).collect();
 
     Ok(strings)
@@ -28,85 +44,175 @@ struct Instruction {
     end: Coordinate,
 }
 
+impl Coordinate {
+    // `X,Y`, both inside the grid
+    fn parse(input: &str) -> Result<Coordinate> {
+        let (x, y) = input
+            .split_once(',')
+            .ok_or_else(|| anyhow!("Expected X,Y, found {:?}", input))?;
+        let value = |value: &str| match value.parse::<usize>() {
+            Ok(value) if value < GRID_SIZE => Ok(value),
+            Ok(value) => Err(anyhow!(
+                "Coordinate {} is outside the {}x{} grid",
+                value,
+                GRID_SIZE,
+                GRID_SIZE
+            )),
+            Err(e) => Err(anyhow!("Invalid coordinate {:?}: {}", value, e)),
+        };
+        Ok(Coordinate {
+            x: value(x)?,
+            y: value(y)?,
+        })
+    }
+}
+
 impl Instruction {
-    fn from_str(input: &str) -> Option<Instruction> {
+    fn from_str(input: &str) -> Result<Instruction> {
         let segments: Vec<&str> = input.split_whitespace().collect();
 
-        let (action, init_coordinates_position) = match segments[0] {
-            "toggle" => (Action::Toggle, 1),
-            "turn" => match segments[1] {
-                "on" => (Action::TurnOn, 2),
-                "off" => (Action::TurnOff, 2),
-                _ => return None,
-            },
-            _ => return None,
+        let (action, start, end) = match segments[..] {
+            ["toggle", start, "through", end] => (Action::Toggle, start, end),
+            ["turn", "on", start, "through", end] => (Action::TurnOn, start, end),
+            ["turn", "off", start, "through", end] => (Action::TurnOff, start, end),
+            _ => return Err(anyhow!("Invalid instruction {:?}", input)),
         };
 
-        let origin_coordinates: Vec<&str> =
-            segments[init_coordinates_position].split(",").collect();
-        let end_coordinates: Vec<&str> =
-            segments[init_coordinates_position + 2].split(",").collect();
-
-        Some(Instruction {
+        Ok(Instruction {
             action,
-            start: Coordinate {
-                x: origin_coordinates[0].parse().ok()?,
-                y: origin_coordinates[1].parse().ok()?,
-            },
-            end: Coordinate {
-                x: end_coordinates[0].parse().ok()?,
-                y: end_coordinates[1].parse().ok()?,
-            },
+            start: Coordinate::parse(start)?,
+            end: Coordinate::parse(end)?,
         })
     }
 }
 
-pub fn solve_part1(commands: &Vec<String>) -> u32 {
-    let mut grid: Vec<Vec<u32>> = vec![vec![0; 1000]; 1000];
+// Apply `update` to every light of the instruction's rectangle. Rows are independent,
+// so with the `parallel` feature they are updated concurrently.
+fn update_lights(
+    grid: &mut [Vec<u32>],
+    instruction: &Instruction,
+    update: impl Fn(&mut u32) + Sync,
+) {
+    // A reversed rectangle holds no lights
+    if instruction.start.x > instruction.end.x || instruction.start.y > instruction.end.y {
+        return;
+    }
+    let columns = instruction.start.x..=instruction.end.x;
+    let rows = &mut grid[instruction.start.y..=instruction.end.y];
+    #[cfg(not(feature = "parallel"))]
+    let rows = rows.iter_mut();
+    #[cfg(feature = "parallel")]
+    let rows = rows.par_iter_mut();
+
+    rows.for_each(|row| row[columns.clone()].iter_mut().for_each(&update));
+}
+
+// Brightness of every light once all commands are followed, under either part's rules
+pub fn final_grid(commands: &[String], part: Part) -> Vec<Vec<u32>> {
+    let mut grid: Vec<Vec<u32>> = vec![vec![0; GRID_SIZE]; GRID_SIZE];
 
     for command in commands {
-        if let Some(instruction) = Instruction::from_str(command) {
-            for i in instruction.start.y..=instruction.end.y {
-                for j in instruction.start.x..=instruction.end.x {
-                    match instruction.action {
+        if let Ok(instruction) = Instruction::from_str(command) {
+            match part {
+                Part::One => {
+                    update_lights(&mut grid, &instruction, |light| match instruction.action {
                         Action::Toggle => {
-                            if grid[i][j] == 0 {
-                                grid[i][j] = 1
+                            if *light == 0 {
+                                *light = 1
                             } else {
-                                grid[i][j] = 0
+                                *light = 0
                             }
                         }
-                        Action::TurnOn => grid[i][j] = 1,
-                        Action::TurnOff => grid[i][j] = 0,
-                    }
+                        Action::TurnOn => *light = 1,
+                        Action::TurnOff => *light = 0,
+                    })
                 }
-            }
-        }
-    }
-    grid.iter().flatten().sum()
-}
-
-pub fn solve_part2(commands: &Vec<String>) -> u32 {
-    let mut grid: Vec<Vec<u32>> = vec![vec![0; 1000]; 1000];
-
-    for command in commands {
-        if let Some(instruction) = Instruction::from_str(command) {
-            for i in instruction.start.y..=instruction.end.y {
-                for j in instruction.start.x..=instruction.end.x {
-                    match instruction.action {
-                        Action::Toggle => grid[i][j] = grid[i][j] + 2,
-                        Action::TurnOn => grid[i][j] = grid[i][j] + 1,
+                Part::Two => {
+                    update_lights(&mut grid, &instruction, |light| match instruction.action {
+                        Action::Toggle => *light += 2,
+                        Action::TurnOn => *light += 1,
                         Action::TurnOff => {
-                            if grid[i][j] != 0 {
-                                grid[i][j] = grid[i][j] - 1
+                            if *light != 0 {
+                                *light -= 1
                             }
                         }
-                    }
+                    })
                 }
             }
         }
     }
-    grid.iter().flatten().sum()
+    grid
+}
+
+// Run-length encoded rows, identical neighbouring rows merged:
+// "rows 1-998: 1x1000" or "row 499: 1x499 0x2 1x499"
+pub fn describe_grid(grid: &[Vec<u32>]) -> String {
+    let mut lines = Vec::new();
+    let mut first = 0;
+    for (last, rows) in grid.chunk_by(|a, b| a == b).scan(0, |start, rows| {
+        *start += rows.len();
+        Some((*start - 1, rows))
+    }) {
+        let runs: Vec<String> = rows[0]
+            .chunk_by(|a, b| a == b)
+            .map(|run| format!("{}x{}", run[0], run.len()))
+            .collect();
+        let label = if first == last {
+            format!("row {}", first)
+        } else {
+            format!("rows {}-{}", first, last)
+        };
+        lines.push(format!("{}: {}", label, runs.join(" ")));
+        first = last + 1;
+    }
+    lines.join("\n")
+}
+
+pub fn solve_part1(commands: &[String]) -> u32 {
+    final_grid(commands, Part::One).iter().flatten().sum()
+}
+
+pub fn solve_part2(commands: &[String]) -> u32 {
+    final_grid(commands, Part::Two).iter().flatten().sum()
+}
+
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 6,
+    title: "Probably a Fire Hazard",
+    input: "One instruction per line, e.g. `toggle 0,0 through 999,0`",
+    part1: Answer {
+        label: "lights lit",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "total brightness",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::Grid, Tag::Parsing, Tag::Simulation],
+};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = u32;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        parse(input)
+    }
+
+    fn part1(commands: &Vec<String>) -> u32 {
+        solve_part1(commands)
+    }
+
+    fn part2(commands: &Vec<String>) -> u32 {
+        solve_part2(commands)
+    }
 }
 
 #[cfg(test)]
@@ -117,7 +223,7 @@ mod tests {
     fn test_solve_part1() {
         // Test case 1: turn on all lights
         let input1 = "turn on 0,0 through 999,999";
-        let result1 = solve_part1(&vec![input1.to_string()]);
+        let result1 = solve_part1(&[input1.to_string()]);
         assert_eq!(result1, 1_000_000, "All lights should be on");
 
         // Test case 2: toggle first line
@@ -156,12 +262,12 @@ mod tests {
     fn test_solve_part2() {
         // Test case 1: Single light increase
         let input1 = "turn on 0,0 through 0,0";
-        let result1 = solve_part2(&vec![input1.to_string()]);
+        let result1 = solve_part2(&[input1.to_string()]);
         assert_eq!(result1, 1, "Single light should increase by 1");
 
         // Test case 2: Toggle all lights
         let input2 = "toggle 0,0 through 999,999";
-        let result2 = solve_part2(&vec![input2.to_string()]);
+        let result2 = solve_part2(&[input2.to_string()]);
         assert_eq!(result2, 2_000_000, "Toggle should increase all lights by 2");
 
         // Test case 3: Combined operations
@@ -175,4 +281,51 @@ mod tests {
             "Should handle combined operations correctly"
         );
     }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("toggle 0,0 through 999,0\n").unwrap().len(), 1);
+
+        let error = |input: &str| format!("{:#}", parse(input).unwrap_err());
+        assert_eq!(
+            error("toggle 0,0 through 1,1\nturn 0,0 through 1,1"),
+            "Line 2: Invalid instruction \"turn 0,0 through 1,1\""
+        );
+        assert_eq!(
+            error("turn on 0,0 through 1000,5"),
+            "Line 1: Coordinate 1000 is outside the 1000x1000 grid"
+        );
+        assert_eq!(
+            error("turn off 0;0 through 1,1"),
+            "Line 1: Expected X,Y, found \"0;0\""
+        );
+        assert_eq!(
+            error("toggle 0,x through 1,1"),
+            "Line 1: Invalid coordinate \"x\": invalid digit found in string"
+        );
+        assert!(parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_reversed_rectangle() {
+        let commands = parse("turn on 5,5 through 1,1\ntoggle 2,0 through 3,0").unwrap();
+        assert_eq!(solve_part1(&commands), 2);
+        assert_eq!(solve_part2(&commands), 4);
+    }
+
+    #[test]
+    fn test_final_grid_snapshot() {
+        let commands = vec![
+            "turn on 0,0 through 999,999".to_string(),
+            "toggle 0,0 through 999,0".to_string(),
+            "turn off 499,499 through 500,500".to_string(),
+        ];
+        insta::assert_snapshot!("part1", describe_grid(&final_grid(&commands, Part::One)));
+
+        let commands = vec![
+            "turn on 0,0 through 0,0".to_string(),
+            "toggle 0,0 through 999,999".to_string(),
+        ];
+        insta::assert_snapshot!("part2", describe_grid(&final_grid(&commands, Part::Two)));
+    }
 }
@@ -1,6 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 use anyhow::{anyhow, Context, Result};
-use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs::read_to_string;
+use aoc_common::{Answer, AnswerKind, Explain, Metadata, Part, Solution, Tag};
+use core::fmt;
 
 // define types for the circuit
 #[derive(Debug, Clone)]
@@ -25,8 +34,50 @@ pub struct Instruction {
     pub target: String,
 }
 
-pub fn parse_input(input_path: String) -> Result<Vec<String>, anyhow::Error> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signal::Value(value) => write!(f, "{}", value),
+            Signal::Wire(wire) => write!(f, "{}", wire),
+        }
+    }
+}
+
+// Written back in the puzzle's own syntax
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operation::Assign(signal) => write!(f, "{}", signal),
+            Operation::And(left, right) => write!(f, "{} AND {}", left, right),
+            Operation::Or(left, right) => write!(f, "{} OR {}", left, right),
+            Operation::LShift(signal, amount) => write!(f, "{} LSHIFT {}", signal, amount),
+            Operation::RShift(signal, amount) => write!(f, "{} RSHIFT {}", signal, amount),
+            Operation::Not(signal) => write!(f, "NOT {}", signal),
+        }
+    }
+}
+
+impl Operation {
+    // Wires this operation reads from
+    fn input_wires(&self) -> Vec<&str> {
+        let signals = match self {
+            Operation::Assign(signal)
+            | Operation::LShift(signal, _)
+            | Operation::RShift(signal, _)
+            | Operation::Not(signal) => vec![signal],
+            Operation::And(left, right) | Operation::Or(left, right) => vec![left, right],
+        };
+        signals
+            .into_iter()
+            .filter_map(|signal| match signal {
+                Signal::Wire(wire) => Some(wire.as_str()),
+                Signal::Value(_) => None,
+            })
+            .collect()
+    }
+}
+
+pub fn parse(input_contents: &str) -> Result<Vec<String>, anyhow::Error> {
     let strings: Vec<String> = input_contents.lines().map(String::from).collect();
 
     Ok(strings)
@@ -86,8 +137,8 @@ pub fn parse_instruction(instruction_str: &str) -> Result<Instruction, anyhow::E
 // Evaluate a wire's value with stack-based DFS
 fn evaluate_wire(
     wire: &str,
-    instructions_map: &HashMap<String, Instruction>,
-    memo: &mut HashMap<String, u16>,
+    instructions_map: &BTreeMap<String, Instruction>,
+    memo: &mut BTreeMap<String, u16>,
 ) -> Result<u16> {
     // Return memoized value if available
     if let Some(&value) = memo.get(wire) {
@@ -95,8 +146,8 @@ fn evaluate_wire(
     }
 
     let mut stack = VecDeque::new();
-    let mut in_progress = HashSet::new();
-    let mut ready = HashSet::new();
+    let mut in_progress = BTreeSet::new();
+    let mut ready = BTreeSet::new();
 
     // Add the starting wire to our stack
     stack.push_back((wire.to_string(), false));
@@ -199,7 +250,7 @@ fn evaluate_wire(
 }
 
 // Helper function to evaluate signals (either direct values or wire references)
-fn evaluate_signal(signal: &Signal, memo: &HashMap<String, u16>) -> Result<u16> {
+fn evaluate_signal(signal: &Signal, memo: &BTreeMap<String, u16>) -> Result<u16> {
     match signal {
         Signal::Value(val) => Ok(*val),
         Signal::Wire(wire) => memo
@@ -209,72 +260,174 @@ fn evaluate_signal(signal: &Signal, memo: &HashMap<String, u16>) -> Result<u16>
     }
 }
 
-pub fn solve_part1(lines: &Vec<String>) -> u16 {
-    // Parse instructions
-    let mut instructions = Vec::new();
-    for line in lines {
-        match parse_instruction(line) {
-            Ok(inst) => instructions.push(inst),
-            Err(e) => {
-                eprintln!("Error parsing instruction '{}': {}", line, e);
-                continue;
-            }
-        }
+// A parsed circuit: every wire mapped to the instruction driving it
+#[derive(Debug, Clone, Default)]
+pub struct Circuit {
+    instructions: BTreeMap<String, Instruction>,
+}
+
+impl Circuit {
+    pub fn new(instructions: Vec<Instruction>) -> Circuit {
+        let instructions = instructions
+            .into_iter()
+            .map(|inst| (inst.target.clone(), inst))
+            .collect();
+        Circuit { instructions }
     }
 
-    // Build a map of wire -> instruction
-    let instructions_map: HashMap<String, Instruction> = instructions
-        .iter()
-        .map(|inst| (inst.target.clone(), inst.clone()))
-        .collect();
+    // Parse a circuit, failing on the first invalid instruction
+    pub fn from_lines(lines: &[String]) -> Result<Circuit> {
+        let instructions = lines
+            .iter()
+            .map(|line| {
+                parse_instruction(line)
+                    .with_context(|| format!("Error parsing instruction '{}'", line))
+            })
+            .collect::<Result<Vec<Instruction>>>()?;
+        Ok(Circuit::new(instructions))
+    }
 
-    // Evaluate and return the value of wire 'a'
-    let mut memo = HashMap::new();
-    match evaluate_wire("a", &instructions_map, &mut memo) {
-        Ok(value) => value,
-        Err(e) => {
-            eprintln!("Error evaluating wire 'a': {}", e);
-            0
+    pub fn wires(&self) -> impl Iterator<Item = &str> {
+        self.instructions.keys().map(String::as_str)
+    }
+
+    // Compute the signal ultimately provided to a wire
+    pub fn evaluate(&self, wire: &str) -> Result<u16> {
+        let mut memo = BTreeMap::new();
+        evaluate_wire(wire, &self.instructions, &mut memo)
+    }
+
+    // Signal on every wire, as memoized while evaluating them
+    pub fn signals(&self) -> Result<BTreeMap<String, u16>> {
+        let mut memo = BTreeMap::new();
+        for wire in self.instructions.keys() {
+            evaluate_wire(wire, &self.instructions, &mut memo)?;
         }
+        Ok(memo)
     }
-}
 
-pub fn solve_part2(lines: &Vec<String>) -> u16 {
-    // Parse instructions
-    let mut instructions = Vec::new();
-    for line in lines {
-        match parse_instruction(line) {
-            Ok(inst) => instructions.push(inst),
-            Err(e) => {
-                eprintln!("Error parsing instruction '{}': {}", line, e);
+    // Replace whatever drives a wire with a constant signal
+    pub fn set_signal(&mut self, wire: &str, value: u16) {
+        self.instructions.insert(
+            wire.to_string(),
+            Instruction {
+                operation: Operation::Assign(Signal::Value(value)),
+                target: wire.to_string(),
+            },
+        );
+    }
+
+    // Dependency chain of a wire, one line per wire with the signal it carries.
+    // Wires feeding several gates are only expanded the first time they appear.
+    pub fn explain(&self, wire: &str) -> Result<String> {
+        let mut memo = BTreeMap::new();
+        evaluate_wire(wire, &self.instructions, &mut memo)?;
+
+        let mut lines = Vec::new();
+        let mut expanded = BTreeSet::new();
+        let mut stack = vec![(wire, 0)];
+
+        while let Some((current_wire, depth)) = stack.pop() {
+            let indent = "  ".repeat(depth);
+            let value = memo[current_wire];
+            if !expanded.insert(current_wire) {
+                lines.push(format!(
+                    "{}{} = {} (see above)",
+                    indent, current_wire, value
+                ));
                 continue;
             }
+
+            let operation = &self.instructions[current_wire].operation;
+            lines.push(format!(
+                "{}{} = {} <- {}",
+                indent, current_wire, value, operation
+            ));
+            for dep_wire in operation.input_wires().into_iter().rev() {
+                stack.push((dep_wire, depth + 1));
+            }
         }
+
+        Ok(lines.join("\n"))
     }
+}
 
-    // First, calculate the value of wire 'a'
-    let a_value = solve_part1(lines);
+// Signal on wire a once b is overridden with a's original signal
+fn signal_with_b_overridden(circuit: &Circuit) -> Result<u16> {
+    let mut circuit = circuit.clone();
+    circuit.set_signal("b", circuit.evaluate("a")?);
+    circuit.evaluate("a")
+}
 
-    // Now, override wire 'b' with the value of 'a'
-    for inst in &mut instructions {
-        if inst.target == "b" {
-            inst.operation = Operation::Assign(Signal::Value(a_value));
-        }
+pub fn solve_part1(lines: &[String]) -> Result<u16> {
+    Circuit::from_lines(lines)?.evaluate("a")
+}
+
+pub fn solve_part2(lines: &[String]) -> Result<u16> {
+    signal_with_b_overridden(&Circuit::from_lines(lines)?)
+}
+
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 7,
+    title: "Some Assembly Required",
+    input: "One wire connection per line, e.g. `x AND y -> d`",
+    part1: Answer {
+        label: "signal on wire a",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "signal on wire a once b is overridden",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::Graph, Tag::Parsing, Tag::Simulation],
+};
+
+pub struct Day07;
+
+// Overriding b only cuts wires out of a's chain, so once a evaluates both parts do
+const EVALUATED_IN_PARSE: &str = "wire a was evaluated when the circuit was parsed";
+
+impl Solution for Day07 {
+    type Input = Circuit;
+    type Part1 = u16;
+    type Part2 = u16;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    // Circuits where wire a can't be evaluated are rejected here, so the parts can't fail
+    fn parse(input: &str) -> Result<Circuit> {
+        let circuit = Circuit::from_lines(&parse(input)?)?;
+        circuit.evaluate("a")?;
+        Ok(circuit)
     }
 
-    // Build a map of wire -> instruction with the modified instructions
-    let instructions_map: HashMap<String, Instruction> = instructions
-        .iter()
-        .map(|inst| (inst.target.clone(), inst.clone()))
-        .collect();
+    fn part1(circuit: &Circuit) -> u16 {
+        circuit.evaluate("a").expect(EVALUATED_IN_PARSE)
+    }
 
-    // Evaluate and return the new value of wire 'a'
-    let mut memo = HashMap::new();
-    match evaluate_wire("a", &instructions_map, &mut memo) {
-        Ok(value) => value,
-        Err(e) => {
-            eprintln!("Error evaluating wire 'a' in part 2: {}", e);
-            0
+    fn part2(circuit: &Circuit) -> u16 {
+        signal_with_b_overridden(circuit).expect(EVALUATED_IN_PARSE)
+    }
+}
+
+impl Explain for Day07 {
+    fn explain(circuit: &Circuit, part: Part) -> String {
+        let mut circuit = circuit.clone();
+        let mut header = String::new();
+        if part == Part::Two {
+            let a_value = Day07::part1(&circuit);
+            circuit.set_signal("b", a_value);
+            header = format!(
+                "Wire b overridden with the part 1 signal of a ({})\n",
+                a_value
+            );
+        }
+
+        match circuit.explain("a") {
+            Ok(chain) => header + &chain,
+            Err(e) => format!("{}Could not evaluate wire 'a': {}", header, e),
         }
     }
 }
@@ -369,4 +522,90 @@ mod tests {
 
         Ok(())
     }
+    #[test]
+    fn test_circuit() -> Result<()> {
+        let lines: Vec<String> = [
+            "123 -> x",
+            "456 -> y",
+            "x AND y -> d",
+            "x OR y -> e",
+            "x LSHIFT 2 -> f",
+            "y RSHIFT 2 -> g",
+            "NOT x -> h",
+            "NOT y -> i",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+
+        let mut circuit = Circuit::from_lines(&lines)?;
+        assert_eq!(circuit.evaluate("d")?, 72);
+        assert_eq!(circuit.evaluate("e")?, 507);
+        assert_eq!(circuit.evaluate("f")?, 492);
+        assert_eq!(circuit.evaluate("g")?, 114);
+        assert_eq!(circuit.evaluate("h")?, 65412);
+        assert_eq!(circuit.evaluate("i")?, 65079);
+        assert!(circuit.evaluate("missing").is_err());
+
+        circuit.set_signal("x", 1);
+        assert_eq!(circuit.evaluate("h")?, 65534);
+
+        assert!(Circuit::from_lines(&["x INVALID y -> z".to_string()]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_circuits() {
+        let error = |input: &str| format!("{:#}", <Day07 as Solution>::parse(input).unwrap_err());
+        assert!(error("123 -> b\nx INVALID y -> a").starts_with("Error parsing instruction"));
+        assert!(<Day07 as Solution>::parse("123 -> b").is_err());
+
+        let lines = ["x INVALID y -> a".to_string()];
+        assert!(solve_part1(&lines).is_err());
+        assert!(solve_part2(&lines).is_err());
+
+        let lines = ["b -> a".to_string(), "7 -> b".to_string()];
+        assert_eq!(solve_part1(&lines).unwrap(), 7);
+        assert_eq!(solve_part2(&lines).unwrap(), 7);
+    }
+    #[test]
+    fn test_explain() -> Result<()> {
+        let lines: Vec<String> = ["123 -> x", "456 -> y", "x AND y -> d", "d OR x -> a"]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        let circuit = Circuit::from_lines(&lines)?;
+        let expected = [
+            "a = 123 <- d OR x",
+            "  d = 72 <- x AND y",
+            "    x = 123 <- 123",
+            "    y = 456 <- 456",
+            "  x = 123 (see above)",
+        ];
+        assert_eq!(circuit.explain("a")?, expected.join("\n"));
+
+        let explanation = Day07::explain(&circuit, Part::Two);
+        assert!(explanation.starts_with("Wire b overridden with the part 1 signal of a (123)"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_signals_snapshot() -> Result<()> {
+        let lines: Vec<String> = [
+            "123 -> x",
+            "456 -> y",
+            "x AND y -> d",
+            "x OR y -> e",
+            "x LSHIFT 2 -> f",
+            "y RSHIFT 2 -> g",
+            "NOT x -> h",
+            "NOT y -> i",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        insta::assert_debug_snapshot!(Circuit::from_lines(&lines)?.signals()?);
+        Ok(())
+    }
 }
@@ -1,8 +1,13 @@
-use anyhow::{Context, Result};
-use std::fs::read_to_string;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub fn parse_input(input_path: String) -> Result<Vec<String>> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use anyhow::Result;
+use aoc_common::{Answer, AnswerKind, Metadata, Solution, Tag};
+
+pub fn parse(input_contents: &str) -> Result<Vec<String>> {
     let strings: Vec<String> = input_contents.lines().map(String::from).collect();
 
     Ok(strings)
@@ -98,6 +103,45 @@ fn encoded_length(s: &str) -> usize {
     count
 }
 
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 8,
+    title: "Matchsticks",
+    input: "One string literal per line",
+    part1: Answer {
+        label: "code minus in-memory characters",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "encoded minus code characters",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::Parsing, Tag::Strings],
+};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> Result<Vec<String>> {
+        parse(input)
+    }
+
+    fn part1(strings: &Vec<String>) -> usize {
+        solve_part1(strings)
+    }
+
+    fn part2(strings: &Vec<String>) -> usize {
+        solve_part2(strings)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,18 +1,30 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 use anyhow::{Context, Result};
+use aoc_common::{Answer, AnswerKind, Explain, Metadata, Part, Solution, Tag};
+use core::iter;
 use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
-use std::fs::read_to_string;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-pub fn parse_input(input_path: String) -> Result<Vec<String>> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
+pub fn parse(input_contents: &str) -> Result<Vec<String>> {
     let strings: Vec<String> = input_contents.lines().map(String::from).collect();
 
     Ok(strings)
 }
 
-fn parse_distances(strings: &[String]) -> Result<(HashMap<(String, String), usize>, Vec<String>)> {
-    let mut distances = HashMap::new();
-    let mut cities = HashSet::new();
+type Distances = BTreeMap<(String, String), usize>;
+
+fn parse_distances(strings: &[String]) -> Result<(Distances, Vec<String>)> {
+    let mut distances = BTreeMap::new();
+    let mut cities = BTreeSet::new();
 
     for line in strings {
         let parts: Vec<&str> = line.split(" = ").collect();
@@ -40,21 +52,49 @@ fn parse_distances(strings: &[String]) -> Result<(HashMap<(String, String), usiz
         cities.insert(city_b);
     }
 
+    // Cities come out sorted, so ties between equally good routes always resolve the same way
     let city_list: Vec<String> = cities.into_iter().collect();
     Ok((distances, city_list))
 }
 
+// Distances between cities, parsed once for both parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Map {
+    distances: Distances,
+    cities: Vec<String>,
+}
+
+impl Map {
+    pub fn from_lines(strings: &[String]) -> Result<Map> {
+        let (distances, cities) = parse_distances(strings)?;
+        Ok(Map { distances, cities })
+    }
+
+    pub fn cities(&self) -> &[String] {
+        &self.cities
+    }
+
+    fn optimal_route(&self, find_shortest: bool) -> (usize, Vec<String>) {
+        find_optimal_route(&self.distances, &self.cities, find_shortest)
+    }
+}
+
 fn find_optimal_route(
-    distances: &HashMap<(String, String), usize>,
+    distances: &Distances,
     cities: &[String],
     find_shortest: bool,
 ) -> (usize, Vec<String>) {
-    let routes = cities.iter().permutations(cities.len()).map(|route| {
-        let distance = calculate_route_distance(&route, distances);
-        let route_owned: Vec<String> = route.into_iter().cloned().collect();
-        (distance, route_owned)
-    });
+    // Routes are grouped by their first city, groups being searched in parallel with
+    // the `parallel` feature. Keeping the groups in order picks the same route either way.
+    let first_cities = 0..cities.len();
+    #[cfg(feature = "parallel")]
+    let first_cities = first_cities.into_par_iter();
 
+    let best_routes: Vec<(usize, Vec<String>)> = first_cities
+        .filter_map(|first| optimal_route_from(first, distances, cities, find_shortest))
+        .collect();
+
+    let routes = best_routes.into_iter();
     if find_shortest {
         routes
             .min_by_key(|(distance, _)| *distance)
@@ -66,10 +106,37 @@ fn find_optimal_route(
     }
 }
 
-fn calculate_route_distance(
-    route: &[&String],
-    distances: &HashMap<(String, String), usize>,
-) -> usize {
+fn optimal_route_from(
+    first: usize,
+    distances: &Distances,
+    cities: &[String],
+    find_shortest: bool,
+) -> Option<(usize, Vec<String>)> {
+    let others: Vec<&String> = cities
+        .iter()
+        .enumerate()
+        .filter(|&(index, _)| index != first)
+        .map(|(_, city)| city)
+        .collect();
+
+    let routes = others
+        .iter()
+        .copied()
+        .permutations(others.len())
+        .map(|rest| {
+            let route: Vec<&String> = iter::once(&cities[first]).chain(rest).collect();
+            (calculate_route_distance(&route, distances), route)
+        });
+
+    let (distance, route) = if find_shortest {
+        routes.min_by_key(|(distance, _)| *distance)?
+    } else {
+        routes.max_by_key(|(distance, _)| *distance)?
+    };
+    Some((distance, route.into_iter().cloned().collect()))
+}
+
+fn calculate_route_distance(route: &[&String], distances: &Distances) -> usize {
     route
         .windows(2)
         .map(|pair| {
@@ -81,18 +148,86 @@ fn calculate_route_distance(
         .sum()
 }
 
-pub fn solve_part1(strings: &[String]) -> usize {
-    let (distances, cities) = parse_distances(strings).unwrap();
-    // let (shortest_distance, _route) = find_shortest_route(&distances, &cities);
-    let (shortest_distance, _route) = find_optimal_route(&distances, &cities, true);
+// Shortest route visiting every city once, with its total distance
+pub fn shortest_route(strings: &[String]) -> Result<(usize, Vec<String>)> {
+    Ok(Map::from_lines(strings)?.optimal_route(true))
+}
+
+// Longest route visiting every city once, with its total distance
+pub fn longest_route(strings: &[String]) -> Result<(usize, Vec<String>)> {
+    Ok(Map::from_lines(strings)?.optimal_route(false))
+}
+
+pub fn solve_part1(strings: &[String]) -> Result<usize> {
+    let (shortest_distance, _route) = shortest_route(strings)?;
+    Ok(shortest_distance)
+}
+
+pub fn solve_part2(strings: &[String]) -> Result<usize> {
+    let (longest_distance, _route) = longest_route(strings)?;
+    Ok(longest_distance)
+}
+
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 9,
+    title: "All in a Single Night",
+    input: "One distance per line, e.g. `London to Dublin = 464`",
+    part1: Answer {
+        label: "shortest route",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "longest route",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::BruteForce, Tag::Graph],
+};
+
+pub struct Day09;
 
-    shortest_distance
+impl Solution for Day09 {
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> Result<Map> {
+        Map::from_lines(&parse(input)?)
+    }
+
+    fn part1(map: &Map) -> usize {
+        map.optimal_route(true).0
+    }
+
+    fn part2(map: &Map) -> usize {
+        map.optimal_route(false).0
+    }
 }
 
-pub fn solve_part2(strings: &[String]) -> usize {
-    let (distances, cities) = parse_distances(strings).unwrap();
-    let (longest_distance, _route) = find_optimal_route(&distances, &cities, false);
-    longest_distance
+impl Explain for Day09 {
+    fn explain(map: &Map, part: Part) -> String {
+        let Map { distances, cities } = map;
+        let find_shortest = part == Part::One;
+        let (total, route) = map.optimal_route(find_shortest);
+
+        let mut lines = vec![format!(
+            "{} route through {} cities:",
+            if find_shortest { "Shortest" } else { "Longest" },
+            cities.len()
+        )];
+        for pair in route.windows(2) {
+            let distance = distances
+                .get(&(pair[0].clone(), pair[1].clone()))
+                .copied()
+                .unwrap_or(0);
+            lines.push(format!("  {} -> {} = {}", pair[0], pair[1], distance));
+        }
+        lines.push(format!("Total distance: {}", total));
+        lines.join("\n")
+    }
 }
 
 #[cfg(test)]
@@ -136,7 +271,7 @@ mod tests {
 
     #[test]
     fn test_calculate_route_distance() {
-        let mut distances = HashMap::new();
+        let mut distances = BTreeMap::new();
         distances.insert(("A".to_string(), "B".to_string()), 10);
         distances.insert(("B".to_string(), "C".to_string()), 5);
 
@@ -151,7 +286,7 @@ mod tests {
 
     #[test]
     fn test_find_optimal_route() {
-        let mut distances = HashMap::new();
+        let mut distances = BTreeMap::new();
         distances.insert(("A".to_string(), "B".to_string()), 10);
         distances.insert(("B".to_string(), "A".to_string()), 10);
         distances.insert(("A".to_string(), "C".to_string()), 15);
@@ -171,4 +306,46 @@ mod tests {
         assert_eq!(longest_distance, 25); // A->C->B or B->C->A
         assert_eq!(longest_route.len(), 3);
     }
+    #[test]
+    fn test_shortest_and_longest_route() {
+        let input = vec![
+            "London to Dublin = 464".to_string(),
+            "London to Belfast = 518".to_string(),
+            "Dublin to Belfast = 141".to_string(),
+        ];
+
+        let (distance, route) = shortest_route(&input).unwrap();
+        assert_eq!(distance, 605);
+        assert_eq!(route, vec!["Belfast", "Dublin", "London"]);
+
+        let (distance, route) = longest_route(&input).unwrap();
+        assert_eq!(distance, 982);
+        assert_eq!(route.len(), 3);
+
+        assert!(shortest_route(&["Invalid line".to_string()]).is_err());
+        assert_eq!(solve_part1(&input).unwrap(), 605);
+        assert_eq!(solve_part2(&input).unwrap(), 982);
+        assert!(solve_part1(&["Invalid line".to_string()]).is_err());
+        assert!(<Day09 as Solution>::parse("London to Dublin = far").is_err());
+    }
+    #[test]
+    fn test_explain() {
+        let input = vec![
+            "London to Dublin = 464".to_string(),
+            "London to Belfast = 518".to_string(),
+            "Dublin to Belfast = 141".to_string(),
+        ];
+
+        let map = Map::from_lines(&input).unwrap();
+        let explanation = Day09::explain(&map, Part::One);
+        let lines: Vec<&str> = explanation.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "Shortest route through 3 cities:");
+        assert!(lines[1..3].iter().any(|leg| leg.ends_with(" = 141")));
+        assert_eq!(lines[3], "Total distance: 605");
+
+        let explanation = Day09::explain(&map, Part::Two);
+        assert!(explanation.starts_with("Longest route through 3 cities:"));
+        assert!(explanation.ends_with("Total distance: 982"));
+    }
 }
@@ -1,48 +1,66 @@
-use std::fs::read_to_string;
-use anyhow::Context;
+#![cfg_attr(not(feature = "std"), no_std)]
 
-pub fn parse_input(input_path: String) -> anyhow::Result<String> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
+extern crate alloc;
 
-    Ok(input_contents)
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use aoc_common::{Answer, AnswerKind, Metadata, Solution, Tag};
+
+// Each round grows the chain by about 30%, so 50 rounds of a long seed won't fit in memory
+pub const MAX_SEED_LEN: usize = 32;
+
+pub fn parse(input_contents: &str) -> anyhow::Result<String> {
+    let seed = input_contents.trim();
+    if seed.is_empty() {
+        return Err(anyhow::anyhow!("Expected a seed of digits, found nothing"));
+    }
+    if let Some(c) = seed.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(anyhow::anyhow!("Invalid digit {:?} in seed", c));
+    }
+    if seed.len() > MAX_SEED_LEN {
+        return Err(anyhow::anyhow!(
+            "Seed is {} digits long, at most {} are supported",
+            seed.len(),
+            MAX_SEED_LEN
+        ));
+    }
+    Ok(seed.to_string())
 }
 
-pub fn solve_part1(string: &String) -> usize {
+pub fn solve_part1(string: &str) -> usize {
 
     const ITERATIONS: i32 = 40;
-    let mut chain = string.clone();
+    let mut chain = string.to_string();
     for _i in 0..ITERATIONS {
 
         chain = look_and_say(&chain);
 
     }
-    let length_result = chain.len();
-    println!("{}", length_result);
-    length_result
-
-
+    chain.len()
 }
 
-pub fn solve_part2(string: &String) -> usize {
+pub fn solve_part2(string: &str) -> usize {
     const ITERATIONS: i32 = 50;
-    let mut chain = string.clone();
+    let mut chain = string.to_string();
     for _i in 0..ITERATIONS {
 
         chain = look_and_say(&chain);
 
     }
-    let length_result = chain.len();
-    println!("{}", length_result);
-    length_result
+    chain.len()
 }
 
-fn look_and_say(string: &String) -> String {
+fn look_and_say(string: &str) -> String {
 
     let chars: Vec<char>  = string.chars().collect();
 
-    let mut current_char = chars[0];
+    let Some(&first) = chars.first() else {
+        return String::new();
+    };
+    let mut current_char = first;
     let mut char_count = 0;
-    let mut chain = String::with_capacity((string.len() as f64 * 0.3).ceil() as usize);
+    let mut chain = String::with_capacity((string.len() * 3).div_ceil(10));
 
 
     for c in chars {
@@ -64,11 +82,59 @@ fn look_and_say(string: &String) -> String {
 
 }
 
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 10,
+    title: "Elves Look, Elves Say",
+    input: "A single line of digits",
+    part1: Answer {
+        label: "length after 40 rounds",
+        kind: AnswerKind::Integer,
+    },
+    part2: Answer {
+        label: "length after 50 rounds",
+        kind: AnswerKind::Integer,
+    },
+    tags: &[Tag::Simulation, Tag::Strings],
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> anyhow::Result<String> {
+        parse(input)
+    }
+
+    fn part1(string: &String) -> usize {
+        solve_part1(string)
+    }
+
+    fn part2(string: &String) -> usize {
+        solve_part2(string)
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("1113222113\n").unwrap(), "1113222113");
+        assert!(parse("").is_err());
+        assert!(parse(" \n").is_err());
+        assert!(parse("12a3").is_err());
+        assert!(parse(&"1".repeat(MAX_SEED_LEN + 1)).is_err());
+        assert_eq!(look_and_say(""), "");
+    }
+
     #[test]
     fn test_look_and_say(){
 
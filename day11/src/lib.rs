@@ -1,11 +1,16 @@
-use std::fs::read_to_string;
-use anyhow::Context;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
 
-pub fn parse_input(input_path: String) -> anyhow::Result<String> {
-    let input_contents: String = read_to_string(input_path).context("Could not read input file")?;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::collections::BTreeSet;
+use aoc_common::{Answer, AnswerKind, Explain, Metadata, Part, Solution, Tag};
 
-    Ok(input_contents)
+
+pub fn parse(input_contents: &str) -> anyhow::Result<String> {
+    Ok(input_contents.to_string())
 }
 
 fn increment_password(password: &str) -> String {
@@ -43,7 +48,7 @@ fn has_no_forbidden_chars(password: &str) -> bool {
 }
 
 fn has_two_pairs(password: &str) -> bool {
-    let mut found_pairs = std::collections::HashSet::new();
+    let mut found_pairs = BTreeSet::new();
     let chars: Vec<char> = password.chars().collect();
 
     let mut i = 0;
@@ -65,9 +70,9 @@ fn is_a_good_password(password: &str) -> bool {
         has_two_pairs(password)
 }
 
-pub fn solve_part1(current_password: &String) -> String {
+pub fn solve_part1(current_password: &str) -> String {
 
-    let mut password = current_password.clone();
+    let mut password = current_password.to_string();
     
     loop {
         password = increment_password(&password);
@@ -80,13 +85,109 @@ pub fn solve_part1(current_password: &String) -> String {
 }
 
 
-pub fn solve_part2(string: &String) -> String { 
+pub fn solve_part2(string: &str) -> String {
     
-    let first_valid_password = solve_part1(&string);
+    let first_valid_password = solve_part1(string);
     solve_part1(&first_valid_password)
     
 }
 
+aoc_common::embedded_input!();
+
+pub const METADATA: Metadata = Metadata {
+    day: 11,
+    title: "Corporate Policy",
+    input: "A single line holding the current password",
+    part1: Answer {
+        label: "next password",
+        kind: AnswerKind::Text,
+    },
+    part2: Answer {
+        label: "password after that",
+        kind: AnswerKind::Text,
+    },
+    tags: &[Tag::BruteForce, Tag::Strings],
+};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = String;
+    type Part1 = String;
+    type Part2 = String;
+    const METADATA: Metadata = METADATA;
+    const INPUT: Option<&'static str> = INPUT;
+
+    fn parse(input: &str) -> anyhow::Result<String> {
+        parse(input)
+    }
+
+    fn part1(current_password: &String) -> String {
+        solve_part1(current_password)
+    }
+
+    fn part2(current_password: &String) -> String {
+        solve_part2(current_password)
+    }
+}
+
+// How many candidates each rule turned down on the way to the next password
+#[derive(Debug, Default, PartialEq)]
+struct Rejections {
+    candidates: usize,
+    no_straight: usize,
+    forbidden_chars: usize,
+    no_two_pairs: usize,
+}
+
+fn explain_search(current_password: &str) -> (String, Rejections) {
+    let mut rejections = Rejections::default();
+    let mut password = current_password.to_string();
+
+    loop {
+        password = increment_password(&password);
+        rejections.candidates += 1;
+        if is_a_good_password(&password) {
+            return (password, rejections);
+        }
+        if !has_straight(&password) {
+            rejections.no_straight += 1;
+        }
+        if !has_no_forbidden_chars(&password) {
+            rejections.forbidden_chars += 1;
+        }
+        if !has_two_pairs(&password) {
+            rejections.no_two_pairs += 1;
+        }
+    }
+}
+
+impl Explain for Day11 {
+    fn explain(current_password: &String, part: Part) -> String {
+        let searches = match part {
+            Part::One => 1,
+            Part::Two => 2,
+        };
+        let mut lines = Vec::new();
+        let mut password = current_password.clone();
+
+        for _ in 0..searches {
+            let (next, rejections) = explain_search(&password);
+            lines.push(format!(
+                "{} -> {} after {} candidates, rejected:",
+                password, next, rejections.candidates
+            ));
+            lines.push(format!(
+                "  {} without a straight of three letters",
+                rejections.no_straight
+            ));
+            lines.push(format!("  {} containing i, o or l", rejections.forbidden_chars));
+            lines.push(format!("  {} with fewer than two pairs", rejections.no_two_pairs));
+            password = next;
+        }
+        lines.join("\n")
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -127,4 +228,17 @@ mod tests {
         assert!(!is_a_good_password("abbcegjk")); // no pairs
         assert!(is_a_good_password("abcdffaa")); // valid
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_explain_search() {
+        let (password, rejections) = explain_search("abcdefgh");
+        assert_eq!(password, "abcdffaa");
+        assert_eq!(rejections.candidates, 17413);
+        // Every rejected candidate broke at least one rule
+        let broken = rejections.no_straight + rejections.forbidden_chars + rejections.no_two_pairs;
+        assert!(broken >= rejections.candidates - 1);
+
+        let explanation = Day11::explain(&"abcdefgh".to_string(), Part::One);
+        assert!(explanation.starts_with("abcdefgh -> abcdffaa after 17413 candidates"));
+        assert_eq!(explanation.lines().count(), 4);
+    }
+}